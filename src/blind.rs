@@ -0,0 +1,534 @@
+//! Memorization for solving blindfolded, with every sticker named by a letter
+//!
+//! Pieces are memorized by starting at a buffer, and naming the sticker that belongs where the
+//! buffer is, then the sticker that belongs where that one is, and so on. When the buffer gets
+//! back to its place before every piece is solved, the cycle is broken by starting a new one at
+//! the next unsolved piece. Pieces that are in place but twisted or flipped are listed separately
+use std::fmt::Display;
+
+use thiserror::Error;
+
+pub(crate) use crate::group::Spot;
+use crate::{
+    cube::{Cube, Face, CORNER_FACES, EDGE_FACES},
+    moves::Algorithm,
+    stickers::{Place, Sticker},
+};
+
+/// The order that letters are given to stickers, face by face, going clockwise from the top left
+/// sticker of each face as it is laid out in the printed net
+const FACE_ORDER: [Face; 6] = [Face::U, Face::L, Face::F, Face::R, Face::B, Face::D];
+const CORNER_CELLS: [(usize, usize); 4] = [(0, 0), (0, 2), (2, 2), (2, 0)];
+const EDGE_CELLS: [(usize, usize); 4] = [(0, 1), (1, 2), (2, 1), (1, 0)];
+
+/// Occurs when memo can't be made the way that was asked for
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BlindError {
+    #[error("a letter scheme has 24 letters for corners and 24 for edges, but {0} were given")]
+    WrongLength(usize),
+    #[error("{0} is used for more than one sticker")]
+    RepeatedLetter(char),
+    #[error("{0:?} is not a corner sticker")]
+    NotACorner(Sticker),
+    #[error("{0:?} is not an edge sticker")]
+    NotAnEdge(Sticker),
+    #[error("{0:?} and {1:?} are on the same piece")]
+    SamePiece(Sticker, Sticker),
+    #[error("{0} is not a letter of the letter scheme")]
+    UnknownLetter(char),
+    #[error("no moves were found to solve a target")]
+    NoExecution,
+}
+
+/// Where a sticker is, as a corner or edge [`Spot`]
+fn spot(sticker: Sticker) -> Option<Place> {
+    (sticker.row < 3 && sticker.col < 3).then(|| sticker.place(3))
+}
+
+pub(crate) fn corner_spot(sticker: Sticker) -> Result<Spot, BlindError> {
+    match spot(sticker) {
+        Some(Place::Corner(position, index)) => Ok((position, index)),
+        _ => Err(BlindError::NotACorner(sticker)),
+    }
+}
+
+pub(crate) fn edge_spot(sticker: Sticker) -> Result<Spot, BlindError> {
+    match spot(sticker) {
+        Some(Place::Edge(position, index)) => Ok((position, index)),
+        _ => Err(BlindError::NotAnEdge(sticker)),
+    }
+}
+
+/// The sticker on a corner spot, or on an edge spot
+pub(crate) fn spot_sticker(corners: bool, spot: Spot) -> Sticker {
+    let place = if corners {
+        Place::Corner(spot.0, spot.1)
+    } else {
+        Place::Edge(spot.0, spot.1)
+    };
+    all_stickers()
+        .find(|sticker| sticker.place(3) == place)
+        .expect("every spot has a sticker")
+}
+
+fn all_stickers() -> impl Iterator<Item = Sticker> {
+    Face::ALL.into_iter().flat_map(|face| {
+        (0..3).flat_map(move |row| (0..3).map(move |col| Sticker { face, row, col }))
+    })
+}
+
+/// The letter given to every corner and edge sticker
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{blind::LetterScheme, cube::Face, stickers::Sticker};
+///
+/// let speffz = LetterScheme::speffz();
+/// let uf = Sticker { face: Face::U, row: 2, col: 1 };
+///
+/// assert_eq!(speffz.edge(uf), Some('C'));
+/// assert_eq!(speffz.corner_sticker('C'), Some(Sticker { face: Face::U, row: 2, col: 2 }));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LetterScheme {
+    corners: [[char; 3]; 8],
+    edges: [[char; 2]; 12],
+}
+
+impl LetterScheme {
+    /// Creates a scheme from 24 letters for the corners and 24 for the edges
+    ///
+    /// The faces are lettered in the order U, L, F, R, B, D, and each face goes clockwise from
+    /// its top left sticker, as it is laid out in the printed net
+    ///
+    /// # Errors
+    ///
+    /// This errors when there aren't 24 letters, or a letter is used twice for the same kind of
+    /// piece
+    pub fn new(corners: &str, edges: &str) -> Result<Self, BlindError> {
+        let mut scheme = Self {
+            corners: [[' '; 3]; 8],
+            edges: [[' '; 2]; 12],
+        };
+        for (letters, cells) in [(corners, CORNER_CELLS), (edges, EDGE_CELLS)] {
+            let letters: Vec<char> = letters.chars().filter(|c| !c.is_whitespace()).collect();
+            if letters.len() != 24 {
+                return Err(BlindError::WrongLength(letters.len()));
+            }
+            if let Some(&repeated) = letters
+                .iter()
+                .enumerate()
+                .find_map(|(i, letter)| letters[i + 1..].contains(letter).then_some(letter))
+            {
+                return Err(BlindError::RepeatedLetter(repeated));
+            }
+            let stickers = FACE_ORDER
+                .into_iter()
+                .flat_map(|face| cells.map(|(row, col)| Sticker { face, row, col }));
+            for (sticker, letter) in stickers.zip(letters) {
+                match sticker.place(3) {
+                    Place::Corner(position, index) => scheme.corners[position][index] = letter,
+                    Place::Edge(position, index) => scheme.edges[position][index] = letter,
+                    Place::Center(_) => unreachable!("only corners and edges are lettered"),
+                }
+            }
+        }
+        Ok(scheme)
+    }
+
+    /// The Speffz scheme, which letters each kind of piece from A to X
+    #[must_use]
+    pub const fn speffz() -> Self {
+        Self {
+            corners: [
+                ['C', 'M', 'J'],
+                ['B', 'Q', 'N'],
+                ['W', 'O', 'T'],
+                ['V', 'K', 'P'],
+                ['U', 'G', 'L'],
+                ['X', 'S', 'H'],
+                ['A', 'E', 'R'],
+                ['D', 'I', 'F'],
+            ],
+            edges: [
+                ['B', 'M'],
+                ['C', 'I'],
+                ['D', 'E'],
+                ['A', 'Q'],
+                ['T', 'N'],
+                ['J', 'P'],
+                ['L', 'F'],
+                ['R', 'H'],
+                ['V', 'O'],
+                ['U', 'K'],
+                ['X', 'G'],
+                ['W', 'S'],
+            ],
+        }
+    }
+
+    /// The letter of a corner sticker
+    #[must_use]
+    pub fn corner(&self, sticker: Sticker) -> Option<char> {
+        let (position, index) = corner_spot(sticker).ok()?;
+        Some(self.corners[position][index])
+    }
+
+    /// The letter of an edge sticker
+    #[must_use]
+    pub fn edge(&self, sticker: Sticker) -> Option<char> {
+        let (position, index) = edge_spot(sticker).ok()?;
+        Some(self.edges[position][index])
+    }
+
+    /// The corner sticker with a letter
+    #[must_use]
+    pub fn corner_sticker(&self, letter: char) -> Option<Sticker> {
+        letter_sticker(letter, |sticker| self.corner(sticker))
+    }
+
+    /// The edge sticker with a letter
+    #[must_use]
+    pub fn edge_sticker(&self, letter: char) -> Option<Sticker> {
+        letter_sticker(letter, |sticker| self.edge(sticker))
+    }
+
+    pub(crate) const fn corner_spot_letter(&self, (position, index): Spot) -> char {
+        self.corners[position][index]
+    }
+
+    pub(crate) const fn edge_spot_letter(&self, (position, index): Spot) -> char {
+        self.edges[position][index]
+    }
+}
+
+impl Default for LetterScheme {
+    fn default() -> Self {
+        Self::speffz()
+    }
+}
+
+fn letter_sticker(letter: char, letter_of: impl Fn(Sticker) -> Option<char>) -> Option<Sticker> {
+    all_stickers().find(|&sticker| letter_of(sticker) == Some(letter))
+}
+
+/// How memo is made, which is Speffz with the UF and UFR buffers by default
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MemoOptions {
+    pub letters: LetterScheme,
+    /// The sticker that edges are shot from
+    pub edge_buffer: Sticker,
+    /// The sticker that corners are shot from
+    pub corner_buffer: Sticker,
+}
+
+impl Default for MemoOptions {
+    fn default() -> Self {
+        Self {
+            letters: LetterScheme::speffz(),
+            edge_buffer: Sticker {
+                face: Face::U,
+                row: 2,
+                col: 1,
+            },
+            corner_buffer: Sticker {
+                face: Face::U,
+                row: 2,
+                col: 2,
+            },
+        }
+    }
+}
+
+/// The letters to remember to solve a cube blindfolded
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     blind::{Memo, MemoOptions},
+///     moves::Algorithm,
+/// };
+///
+/// let memo = Memo::scramble(&Algorithm::from("U").unwrap(), &MemoOptions::default()).unwrap();
+///
+/// assert_eq!(memo.edges, vec!['B', 'A', 'D']);
+/// assert_eq!(memo.corners, vec!['B', 'A', 'D']);
+/// assert!(memo.parity);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Memo {
+    /// The edge targets, in the order they are shot to
+    pub edges: Vec<char>,
+    /// The corner targets, in the order they are shot to
+    pub corners: Vec<char>,
+    /// Edges that are in place but flipped, named by the sticker their U or D color is on, or
+    /// their F or B color for edges in the E slice
+    pub flipped_edges: Vec<char>,
+    /// Corners that are in place but twisted, named by the sticker their U or D color is on
+    pub twisted_corners: Vec<char>,
+    /// Whether there is an odd number of targets of each kind, so the last edge and corner
+    /// targets need a parity algorithm
+    pub parity: bool,
+}
+
+impl Memo {
+    /// Memorizes a cube, judging the pieces against its centers
+    ///
+    /// # Errors
+    ///
+    /// This errors when the buffers aren't the right kind of sticker
+    pub fn new(cube: &Cube, options: &MemoOptions) -> Result<Self, BlindError> {
+        let edge_buffer = edge_spot(options.edge_buffer)?;
+        let corner_buffer = corner_spot(options.corner_buffer)?;
+        let letters = &options.letters;
+
+        let (edge_targets, flipped) = trace(&edge_homes(cube), edge_buffer, |spot| {
+            letters.edge_spot_letter(spot)
+        });
+        let (corner_targets, twisted) = trace(&corner_homes(cube), corner_buffer, |spot| {
+            letters.corner_spot_letter(spot)
+        });
+        let edge_letters = |spots: Vec<Spot>| {
+            spots
+                .into_iter()
+                .map(|spot| letters.edge_spot_letter(spot))
+                .collect::<Vec<_>>()
+        };
+        let corner_letters = |spots: Vec<Spot>| {
+            spots
+                .into_iter()
+                .map(|spot| letters.corner_spot_letter(spot))
+                .collect::<Vec<_>>()
+        };
+        Ok(Self {
+            parity: edge_targets.len() % 2 == 1,
+            edges: edge_letters(edge_targets),
+            corners: corner_letters(corner_targets),
+            flipped_edges: edge_letters(flipped),
+            twisted_corners: corner_letters(twisted),
+        })
+    }
+
+    /// Memorizes the cube that a scramble makes from a solved cube
+    ///
+    /// # Errors
+    ///
+    /// This errors when the buffers aren't the right kind of sticker
+    pub fn scramble(scramble: &Algorithm, options: &MemoOptions) -> Result<Self, BlindError> {
+        Self::new(&Cube::new().apply(scramble.clone()), options)
+    }
+}
+
+/// Writes letters in pairs, the way they are usually memorized
+fn pairs(letters: &[char]) -> String {
+    letters
+        .chunks(2)
+        .map(|pair| pair.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Display for Memo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Edges: {}", pairs(&self.edges))?;
+        write!(f, "\nCorners: {}", pairs(&self.corners))?;
+        if !self.flipped_edges.is_empty() {
+            write!(f, "\nFlipped edges: {}", pairs(&self.flipped_edges))?;
+        }
+        if !self.twisted_corners.is_empty() {
+            write!(f, "\nTwisted corners: {}", pairs(&self.twisted_corners))?;
+        }
+        if self.parity {
+            write!(f, "\nParity")?;
+        }
+        Ok(())
+    }
+}
+
+/// For each corner sticker, the spot where the sticker on it belongs
+pub(crate) fn corner_homes(cube: &Cube) -> Vec<Vec<Spot>> {
+    cube.corners
+        .iter()
+        .map(|corner| {
+            let faces = corner.colors.map(|color| cube.face_of(color));
+            let home = CORNER_FACES
+                .iter()
+                .position(|piece| faces.iter().all(|face| piece.contains(face)))
+                .expect("corners have three adjacent colors");
+            faces
+                .iter()
+                .map(|face| {
+                    (
+                        home,
+                        CORNER_FACES[home]
+                            .iter()
+                            .position(|f| f == face)
+                            .unwrap_or(0),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// For each edge sticker, the spot where the sticker on it belongs
+pub(crate) fn edge_homes(cube: &Cube) -> Vec<Vec<Spot>> {
+    cube.edges
+        .iter()
+        .map(|edge| {
+            let faces = edge.colors.map(|color| cube.face_of(color));
+            let home = EDGE_FACES
+                .iter()
+                .position(|piece| faces.iter().all(|face| piece.contains(face)))
+                .expect("edges have two adjacent colors");
+            faces
+                .iter()
+                .map(|face| {
+                    (
+                        home,
+                        EDGE_FACES[home].iter().position(|f| f == face).unwrap_or(0),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Follows the stickers from `buffer`, returning the targets and the pieces that are in place
+/// but turned, where `homes` says where the sticker on each spot belongs
+///
+/// Cycles are broken into the unsolved piece whose first sticker has the earliest letter
+pub(crate) fn trace(
+    homes: &[Vec<Spot>],
+    buffer: Spot,
+    letter: impl Fn(Spot) -> char,
+) -> (Vec<Spot>, Vec<Spot>) {
+    let in_place = |position: usize| homes[position][0].0 == position;
+    let mut visited = vec![false; homes.len()];
+    visited[buffer.0] = true;
+    let mut targets = Vec::new();
+    let mut current = buffer;
+    let mut start = buffer.0;
+    loop {
+        let target = homes[current.0][current.1];
+        if target.0 != start {
+            visited[target.0] = true;
+            targets.push(target);
+            current = target;
+            continue;
+        }
+        if start != buffer.0 {
+            targets.push(target);
+        }
+        let Some(next) = (0..homes.len())
+            .filter(|&position| !visited[position] && !in_place(position))
+            .min_by_key(|&position| letter((position, 0)))
+        else {
+            break;
+        };
+        visited[next] = true;
+        start = next;
+        current = (next, 0);
+        targets.push(current);
+    }
+
+    let turned = (0..homes.len())
+        .filter(|&position| position != buffer.0 && in_place(position) && homes[position][0].1 != 0)
+        .filter_map(|position| {
+            let index = homes[position]
+                .iter()
+                .position(|&home| home == (position, 0))?;
+            Some((position, index))
+        })
+        .collect();
+    (targets, turned)
+}
+
+#[cfg(test)]
+mod blind_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn memo(scramble: &str) -> Memo {
+        Memo::scramble(&Algorithm::from(scramble).unwrap(), &MemoOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn solved_cubes_have_no_memo() {
+        assert_eq!(memo(""), Memo::default());
+        assert_eq!(memo("x y2"), Memo::default());
+    }
+
+    #[test]
+    fn speffz_letters_every_sticker_once() {
+        let speffz = LetterScheme::speffz();
+        let built =
+            LetterScheme::new("ABCDEFGHIJKLMNOPQRSTUVWX", "ABCD EFGH IJKL MNOP QRST UVWX").unwrap();
+
+        assert_eq!(built, speffz);
+    }
+
+    #[test]
+    fn cycles_are_broken_when_the_buffer_is_solved() {
+        let actual = memo("D");
+
+        assert_eq!(actual.edges, vec!['U', 'X', 'W', 'V', 'U']);
+        assert_eq!(actual.corners, vec!['U', 'X', 'W', 'V', 'U']);
+        assert!(actual.parity);
+        assert_str_eq!(
+            actual.to_string(),
+            "Edges: UX WV U\nCorners: UX WV U\nParity"
+        );
+    }
+
+    #[test]
+    fn flipped_edges_are_listed_separately() {
+        let mut facelets: Vec<char> = Cube::new().facelets().chars().collect();
+        for (i, c) in [(5, 'G'), (10, 'Y'), (1, 'O'), (46, 'Y')] {
+            facelets[i] = c;
+        }
+        let cube = Cube::from_facelets(&facelets.into_iter().collect::<String>()).unwrap();
+        let actual = Memo::new(&cube, &MemoOptions::default()).unwrap();
+
+        assert_eq!(actual.edges, Vec::<char>::new());
+        assert_eq!(actual.flipped_edges, vec!['M', 'Q']);
+        assert!(!actual.parity);
+    }
+
+    #[test]
+    fn other_buffers_and_schemes() {
+        let options = MemoOptions {
+            letters: LetterScheme::new("abcdefghijklmnopqrstuvwx", "abcdefghijklmnopqrstuvwx")
+                .unwrap(),
+            edge_buffer: Sticker {
+                face: Face::D,
+                row: 0,
+                col: 1,
+            },
+            ..MemoOptions::default()
+        };
+        let actual = Memo::scramble(&Algorithm::from("D").unwrap(), &options).unwrap();
+
+        assert_eq!(actual.edges, vec!['x', 'w', 'v']);
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        let options = MemoOptions {
+            corner_buffer: MemoOptions::default().edge_buffer,
+            ..MemoOptions::default()
+        };
+
+        assert_eq!(
+            Memo::new(&Cube::new(), &options),
+            Err(BlindError::NotACorner(options.corner_buffer))
+        );
+        assert_eq!(
+            LetterScheme::new("AABCDEFGHIJKLMNOPQRSTUVW", "ABCDEFGHIJKLMNOPQRSTUVWX"),
+            Err(BlindError::RepeatedLetter('A'))
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! Which color goes on which face of a solved cube
+use thiserror::Error;
+
+use crate::cube::{Cube, Face, Side};
+
+/// The colors of each face of a solved cube, as it is held at the start
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{color_scheme::ColorScheme, cube::{Cube, Side}};
+///
+/// let scheme = ColorScheme::wca().held(Side::Yellow, Side::Blue).unwrap();
+/// let cube = Cube::new_with_scheme(scheme);
+///
+/// assert_eq!(scheme.up(), Side::Yellow);
+/// assert_eq!(scheme.front(), Side::Blue);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct ColorScheme {
+    /// The color of each face, in the same order as [`Face`]
+    faces: [Side; 6],
+}
+
+/// Occurs when colors can't be put on the faces of a cube the way that was asked for
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ColorSchemeError {
+    #[error("{0:?} is on more than one face")]
+    RepeatedColor(Side),
+    #[error("{0:?} and {1:?} are not next to each other")]
+    NotAdjacent(Side, Side),
+}
+
+impl ColorScheme {
+    /// Creates a scheme from the color of every face
+    ///
+    /// # Errors
+    ///
+    /// This errors when a color is used for more than one face
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{color_scheme::ColorScheme, cube::Side};
+    ///
+    /// let scheme = ColorScheme::new(
+    ///     Side::White,
+    ///     Side::Yellow,
+    ///     Side::Green,
+    ///     Side::Blue,
+    ///     Side::Orange,
+    ///     Side::Red,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(scheme, ColorScheme::wca());
+    /// ```
+    pub fn new(
+        up: Side,
+        down: Side,
+        front: Side,
+        back: Side,
+        left: Side,
+        right: Side,
+    ) -> Result<Self, ColorSchemeError> {
+        let faces = [up, down, front, back, left, right];
+        for (i, color) in faces.iter().enumerate() {
+            if faces[i + 1..].contains(color) {
+                return Err(ColorSchemeError::RepeatedColor(*color));
+            }
+        }
+        Ok(Self { faces })
+    }
+
+    /// The scheme used by [`Cube::new`](crate::cube::Cube::new), with yellow on top and red in front
+    #[must_use]
+    pub const fn yellow_top() -> Self {
+        Self {
+            faces: [
+                Side::Yellow,
+                Side::White,
+                Side::Red,
+                Side::Orange,
+                Side::Blue,
+                Side::Green,
+            ],
+        }
+    }
+
+    /// The standard scheme held the way WCA scrambles are applied, with white on top and green in front
+    #[must_use]
+    pub const fn wca() -> Self {
+        Self {
+            faces: [
+                Side::White,
+                Side::Yellow,
+                Side::Green,
+                Side::Blue,
+                Side::Orange,
+                Side::Red,
+            ],
+        }
+    }
+
+    /// The Japanese scheme, where blue is opposite white and yellow is opposite green, with white
+    /// on top and green in front
+    #[must_use]
+    pub const fn japanese() -> Self {
+        Self {
+            faces: [
+                Side::White,
+                Side::Blue,
+                Side::Green,
+                Side::Yellow,
+                Side::Orange,
+                Side::Red,
+            ],
+        }
+    }
+
+    /// The same colors, with the cube held so `top` is on top and `front` is in front
+    ///
+    /// # Errors
+    ///
+    /// This errors when `top` and `front` are the same color, or on opposite faces
+    pub fn held(self, top: Side, front: Side) -> Result<Self, ColorSchemeError> {
+        let up = self.face_of(top);
+        let forward = self.face_of(front);
+        if forward == up || forward == up.opposite() {
+            return Err(ColorSchemeError::NotAdjacent(top, front));
+        }
+        let [ux, uy, uz] = up.normal();
+        let [fx, fy, fz] = forward.normal();
+        let right = Face::from_normal([uy * fz - uz * fy, uz * fx - ux * fz, ux * fy - uy * fx]);
+        let color = |face: Face| self.color(face);
+        Ok(Self {
+            faces: [
+                color(up),
+                color(up.opposite()),
+                color(forward),
+                color(forward.opposite()),
+                color(right.opposite()),
+                color(right),
+            ],
+        })
+    }
+
+    /// The color of the U face
+    #[must_use]
+    pub const fn up(self) -> Side {
+        self.color(Face::U)
+    }
+
+    /// The color of the D face
+    #[must_use]
+    pub const fn down(self) -> Side {
+        self.color(Face::D)
+    }
+
+    /// The color of the F face
+    #[must_use]
+    pub const fn front(self) -> Side {
+        self.color(Face::F)
+    }
+
+    /// The color of the B face
+    #[must_use]
+    pub const fn back(self) -> Side {
+        self.color(Face::B)
+    }
+
+    /// The color of the L face
+    #[must_use]
+    pub const fn left(self) -> Side {
+        self.color(Face::L)
+    }
+
+    /// The color of the R face
+    #[must_use]
+    pub const fn right(self) -> Side {
+        self.color(Face::R)
+    }
+
+    /// The colors of the centers of `cube`, as it is held now
+    pub(crate) fn of(cube: &Cube) -> Self {
+        Self {
+            faces: cube.centers.map(|center| center.color),
+        }
+    }
+
+    pub(crate) const fn color(self, face: Face) -> Side {
+        self.faces[face as usize]
+    }
+
+    pub(crate) fn face_of(self, color: Side) -> Face {
+        Face::ALL
+            .into_iter()
+            .find(|&face| self.color(face) == color)
+            .expect("all six colors are used")
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::yellow_top()
+    }
+}
+
+#[cfg(test)]
+mod color_scheme_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn repeated_colors_are_rejected() {
+        let actual = ColorScheme::new(
+            Side::White,
+            Side::Yellow,
+            Side::Green,
+            Side::Blue,
+            Side::Green,
+            Side::Red,
+        );
+        let expected = Err(ColorSchemeError::RepeatedColor(Side::Green));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn yellow_top_is_wca_held_upside_down() {
+        let actual = ColorScheme::wca().held(Side::Yellow, Side::Red).unwrap();
+        let expected = ColorScheme::yellow_top();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn holding_keeps_the_colors_the_same_way_around() {
+        let actual = ColorScheme::wca().held(Side::Green, Side::Red).unwrap();
+        let expected = ColorScheme::new(
+            Side::Green,
+            Side::Blue,
+            Side::Red,
+            Side::Orange,
+            Side::Yellow,
+            Side::White,
+        )
+        .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn opposite_colors_cant_be_held_together() {
+        let actual = ColorScheme::japanese().held(Side::White, Side::Blue);
+        let expected = Err(ColorSchemeError::NotAdjacent(Side::White, Side::Blue));
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,410 @@
+//! Finds commutators that cycle three pieces, for 3-style blindfolded solving
+//!
+//! A commutator `[A, B]` is `A B A' B'`, and a conjugated commutator `[C: [A, B]]` is
+//! `C A B A' B' C'`. The search tries every single turn as `A`, and every single turn or three
+//! move insertion like `R U R'` as `B`, with up to two setup moves. Slice moves are written as
+//! layer turns, like `2R` for the slice next to R
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    sync::OnceLock,
+};
+
+use crate::{
+    blind::{corner_spot, edge_spot, BlindError, Spot},
+    cube::Face,
+    group::Permutation,
+    moves::{Algorithm, LayerTurn, Metric, Move, SliceTurn},
+    stickers::Sticker,
+};
+
+/// A commutator, with the moves that set it up
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Commutator {
+    /// Done before the commutator and undone after it, which is empty for pure commutators
+    pub setup: Algorithm,
+    pub a: Algorithm,
+    pub b: Algorithm,
+}
+
+impl Commutator {
+    /// The moves of the commutator, with the setup done before and undone after
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{commutators::Commutator, moves::Algorithm};
+    ///
+    /// let commutator = Commutator {
+    ///     setup: Algorithm::from("D").unwrap(),
+    ///     a: Algorithm::from("R U R'").unwrap(),
+    ///     b: Algorithm::from("D2").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     commutator.algorithm(),
+    ///     Algorithm::from("D R U R' D2 R U' R' D2 D'").unwrap()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.setup.permute(&self.a.commute(&self.b))
+    }
+}
+
+impl Display for Commutator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.setup == Algorithm::new() {
+            write!(f, "[{}, {}]", self.a, self.b)
+        } else {
+            write!(f, "[{}: [{}, {}]]", self.setup, self.a, self.b)
+        }
+    }
+}
+
+/// The quarter and half turns of every face, and of the slices next to R, U and F
+fn turns() -> Vec<Move> {
+    let faces = Face::ALL
+        .into_iter()
+        .flat_map(|face| (1..=3).map(move |amount| Move::FaceTurn(face.turn(amount))));
+    let slices = [Face::R, Face::U, Face::F].into_iter().flat_map(|face| {
+        (1..=3).map(move |amount| {
+            Move::Layer(LayerTurn {
+                turn: face.turn(amount),
+                depth: 2,
+                wide: false,
+            })
+        })
+    });
+    faces.chain(slices).collect()
+}
+
+/// Which pair of opposite faces a move turns around
+const fn axis(m: Move) -> usize {
+    match m {
+        Move::FaceTurn(turn) | Move::Layer(LayerTurn { turn, .. }) => turn.face().0 as usize / 2,
+        Move::Slice(slice) => slice.layer().turn.face().0 as usize / 2,
+        Move::Rotation(_) => 3,
+    }
+}
+
+/// How many moves are hard to do quickly, which is every move but R, U, D and the M slice
+fn awkward(alg: &Algorithm) -> usize {
+    alg.moves
+        .iter()
+        .filter(|&&m| match m {
+            Move::FaceTurn(turn) => !matches!(turn.face().0, Face::R | Face::U | Face::D),
+            Move::Layer(LayerTurn { turn, .. }) => turn.face().0 != Face::R,
+            Move::Slice(slice) => !matches!(slice, SliceTurn::M(_)),
+            Move::Rotation(_) => true,
+        })
+        .count()
+}
+
+/// Every setup of up to two moves, and every insertion of one move or three moves like `R U R'`
+fn sequences(turns: &[Move], inverted: bool) -> Vec<Algorithm> {
+    let mut sequences: Vec<Algorithm> = turns.iter().map(|&m| vec![m].into()).collect();
+    for &x in turns {
+        for &y in turns.iter().filter(|&&y| axis(y) != axis(x)) {
+            let mut moves = vec![x, y];
+            if inverted {
+                moves.push(x.inverse());
+            }
+            sequences.push(moves.into());
+        }
+    }
+    sequences
+}
+
+/// A pure commutator, and how it moves the pieces
+type Pure = (Algorithm, Algorithm, Permutation);
+
+/// Every pure commutator that cycles three corners, or three edges, which is worked out once
+fn pure_commutators(moved: (usize, usize)) -> &'static [Pure] {
+    static CORNERS: OnceLock<Vec<Pure>> = OnceLock::new();
+    static EDGES: OnceLock<Vec<Pure>> = OnceLock::new();
+    let cache = if moved.0 == 3 { &CORNERS } else { &EDGES };
+    cache.get_or_init(|| {
+        let turns = turns();
+        let insertions = sequences(&turns, true);
+        turns
+            .iter()
+            .flat_map(|&a| {
+                let a: Algorithm = vec![a].into();
+                insertions
+                    .iter()
+                    .flat_map(move |b| [(a.clone(), b.clone()), (b.clone(), a.clone())])
+            })
+            .map(|(a, b)| {
+                let permutation = Permutation::of(&a.commute(&b));
+                (a, b, permutation)
+            })
+            .filter(|(_, _, permutation)| permutation.moved() == Some(moved))
+            .collect()
+    })
+}
+
+/// For each three stickers, the pure commutators that move the sticker on the first to the
+/// second, the second to the third, and the third to the first, which is worked out once
+fn pure_cycles(
+    moved: (usize, usize),
+    origin: Origin,
+    spots: &[Spot],
+) -> &'static HashMap<[Spot; 3], Vec<usize>> {
+    static CORNERS: OnceLock<HashMap<[Spot; 3], Vec<usize>>> = OnceLock::new();
+    static EDGES: OnceLock<HashMap<[Spot; 3], Vec<usize>>> = OnceLock::new();
+    let cache = if moved.0 == 3 { &CORNERS } else { &EDGES };
+    cache.get_or_init(|| {
+        let mut cycles: HashMap<[Spot; 3], Vec<usize>> = HashMap::new();
+        for (index, (_, _, permutation)) in pure_commutators(moved).iter().enumerate() {
+            for &first in spots {
+                let second = origin(permutation, first);
+                if second == first {
+                    continue;
+                }
+                let third = origin(permutation, second);
+                if origin(permutation, third) == first {
+                    cycles
+                        .entry([second, first, third])
+                        .or_default()
+                        .push(index);
+                }
+            }
+        }
+        cycles
+    })
+}
+
+/// No setup, then every setup of up to two moves, with how each moves the pieces, which is
+/// worked out once
+fn setups() -> &'static [(Algorithm, Permutation)] {
+    static SETUPS: OnceLock<Vec<(Algorithm, Permutation)>> = OnceLock::new();
+    SETUPS.get_or_init(|| {
+        std::iter::once(Algorithm::new())
+            .chain(sequences(&turns(), false))
+            .map(|setup| {
+                let permutation = Permutation::of(&setup);
+                (setup, permutation)
+            })
+            .collect()
+    })
+}
+
+/// Every pure commutator that cycles three corners, or three edges, as a library for
+/// [`fmc::insertions`](crate::fmc::insertions)
+///
+/// `A` is a single face or slice turn, and `B` is a single turn or a three move insertion, or the
+/// other way around
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{commutators, moves::Algorithm};
+///
+/// let corners = commutators::three_cycles(true);
+///
+/// assert!(corners.contains(&Algorithm::from("R U R' D R U' R' D'").unwrap()));
+/// ```
+#[must_use]
+pub fn three_cycles(corners: bool) -> Vec<Algorithm> {
+    let moved = if corners { (3, 0) } else { (0, 3) };
+    pure_commutators(moved)
+        .iter()
+        .map(|(a, b, _)| a.commute(b))
+        .collect()
+}
+
+/// Where the sticker on each spot is, of the kind of piece being cycled
+type Origin = fn(&Permutation, Spot) -> Spot;
+
+/// Searches for commutators that move the sticker on `buffer` to `first`, the sticker on `first`
+/// to `second`, and the sticker on `second` to `buffer`, without moving any other piece
+///
+/// The best `limit` commutators are returned, sorted by how many moves they take once moves that
+/// cancel are combined, and then by how many of their moves aren't R, U, D or M
+///
+/// # Errors
+///
+/// This errors when the stickers are not all corners or all edges, or two of them are on the
+/// same piece
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{blind::LetterScheme, commutators};
+///
+/// let speffz = LetterScheme::speffz();
+/// let buffer = speffz.corner_sticker('C').unwrap();
+/// let found = commutators::find(
+///     buffer,
+///     speffz.corner_sticker('B').unwrap(),
+///     speffz.corner_sticker('D').unwrap(),
+///     3,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(found.len(), 3);
+/// assert_eq!(found[0].to_string(), "[R: [B', R F2 R']]");
+/// ```
+pub fn find(
+    buffer: Sticker,
+    first: Sticker,
+    second: Sticker,
+    limit: usize,
+) -> Result<Vec<Commutator>, BlindError> {
+    let (spots, origin, moved, pieces): ([Spot; 3], Origin, _, _) =
+        if let Ok(buffer) = corner_spot(buffer) {
+            let spots = [buffer, corner_spot(first)?, corner_spot(second)?];
+            (spots, Permutation::corner_origin, (3, 0), (8, 3))
+        } else {
+            let spots = [edge_spot(buffer)?, edge_spot(first)?, edge_spot(second)?];
+            (spots, Permutation::edge_origin, (0, 3), (12, 2))
+        };
+    let all_spots: Vec<Spot> = (0..pieces.0)
+        .flat_map(|position| (0..pieces.1).map(move |index| (position, index)))
+        .collect();
+    let [buffer_spot, first_spot, second_spot] = spots;
+    if buffer_spot.0 == first_spot.0 {
+        return Err(BlindError::SamePiece(buffer, first));
+    }
+    if second_spot.0 == buffer_spot.0 {
+        return Err(BlindError::SamePiece(buffer, second));
+    }
+    if second_spot.0 == first_spot.0 {
+        return Err(BlindError::SamePiece(second, first));
+    }
+
+    let pure = pure_commutators(moved);
+    let cycles = pure_cycles(moved, origin, &all_spots);
+
+    let mut found = Vec::new();
+    let mut seen = HashSet::new();
+    for (setup, setup_permutation) in setups() {
+        // where each sticker is taken by the setup
+        let moved_to = |spot: Spot| {
+            all_spots
+                .iter()
+                .copied()
+                .find(|&to| origin(setup_permutation, to) == spot)
+                .unwrap_or(spot)
+        };
+        let [b, t1, t2] = [buffer_spot, first_spot, second_spot].map(moved_to);
+        for &index in cycles.get(&[b, t1, t2]).into_iter().flatten() {
+            let (a, insertion, _) = &pure[index];
+            let commutator = Commutator {
+                setup: setup.clone(),
+                a: a.clone(),
+                b: insertion.clone(),
+            };
+            let simplified = commutator.algorithm().simplify();
+            if seen.insert(simplified.clone()) {
+                let rank = (
+                    simplified.length(Metric::Htm),
+                    awkward(&simplified),
+                    setup.length(Metric::Htm),
+                );
+                found.push((rank, commutator));
+            }
+        }
+    }
+    found.sort_by_cached_key(|(rank, commutator)| (*rank, commutator.to_string()));
+    Ok(found
+        .into_iter()
+        .take(limit)
+        .map(|(_, commutator)| commutator)
+        .collect())
+}
+
+#[cfg(test)]
+mod commutators_tests {
+    use super::*;
+    use crate::blind::{LetterScheme, Memo, MemoOptions};
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn check(corners: bool, letters: [char; 3]) {
+        let speffz = LetterScheme::speffz();
+        let [buffer, first, second] = letters.map(|letter| {
+            if corners {
+                speffz.corner_sticker(letter).unwrap()
+            } else {
+                speffz.edge_sticker(letter).unwrap()
+            }
+        });
+        let found = find(buffer, first, second, 5).unwrap();
+
+        assert!(!found.is_empty(), "no commutators for {letters:?}");
+        for commutator in found {
+            let memo =
+                Memo::scramble(&commutator.algorithm().inverse(), &MemoOptions::default()).unwrap();
+            let targets = if corners {
+                memo.corners.clone()
+            } else {
+                memo.edges.clone()
+            };
+
+            assert_eq!(targets, letters[1..], "{commutator} for {letters:?}");
+            assert_eq!(memo.flipped_edges, Vec::<char>::new());
+            assert_eq!(memo.twisted_corners, Vec::<char>::new());
+        }
+    }
+
+    #[test]
+    fn finds_corner_commutators() {
+        check(true, ['C', 'B', 'D']);
+        check(true, ['C', 'V', 'S']);
+    }
+
+    #[test]
+    fn finds_edge_commutators() {
+        check(false, ['C', 'A', 'U']);
+        check(false, ['C', 'J', 'O']);
+    }
+
+    #[test]
+    fn displays_as_brackets() {
+        let commutator = Commutator {
+            setup: Algorithm::new(),
+            a: Algorithm::from("R U R'").unwrap(),
+            b: Algorithm::from("D").unwrap(),
+        };
+
+        assert_str_eq!(commutator.to_string(), "[R U R', D]");
+    }
+
+    #[test]
+    fn rejects_mixed_stickers() {
+        let speffz = LetterScheme::speffz();
+        let corner = speffz.corner_sticker('C').unwrap();
+        let edge = speffz.edge_sticker('A').unwrap();
+
+        assert_eq!(
+            find(corner, edge, corner, 1),
+            Err(BlindError::NotACorner(edge))
+        );
+        assert_eq!(
+            find(
+                corner,
+                speffz.corner_sticker('M').unwrap(),
+                speffz.corner_sticker('A').unwrap(),
+                1
+            ),
+            Err(BlindError::SamePiece(
+                corner,
+                speffz.corner_sticker('M').unwrap()
+            ))
+        );
+        assert_eq!(
+            find(
+                corner,
+                speffz.corner_sticker('A').unwrap(),
+                speffz.corner_sticker('M').unwrap(),
+                1
+            ),
+            Err(BlindError::SamePiece(
+                corner,
+                speffz.corner_sticker('M').unwrap()
+            ))
+        );
+    }
+}
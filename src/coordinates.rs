@@ -0,0 +1,346 @@
+//! Numbers that describe a cube state, used to store states compactly and to index them
+use crate::{
+    color_scheme::ColorScheme,
+    cube::{Center, Cube, Face, Side, CORNER_FACES, EDGE_FACES},
+};
+
+/// Numbers each ordering of `0..N` from `0` up to `N! - 1`
+pub fn permutation_index<const N: usize>(permutation: &[usize; N]) -> usize {
+    (0..N).fold(0, |index, i| {
+        let smaller = permutation[i + 1..]
+            .iter()
+            .filter(|&&p| p < permutation[i])
+            .count();
+        index * (N - i) + smaller
+    })
+}
+
+/// The ordering of `0..N` that [`permutation_index`] numbers as `index`
+pub fn permutation_from_index<const N: usize>(mut index: usize) -> [usize; N] {
+    let mut digits = [0; N];
+    for i in (0..N).rev() {
+        digits[i] = index % (N - i);
+        index /= N - i;
+    }
+    let mut unused: Vec<usize> = (0..N).collect();
+    digits.map(|digit| unused.remove(digit))
+}
+
+/// Whether an ordering takes an odd number of swaps to put back in order
+pub fn is_odd<const N: usize>(permutation: &[usize; N]) -> bool {
+    let swaps: usize = (0..N)
+        .map(|i| {
+            permutation[i + 1..]
+                .iter()
+                .filter(|&&p| p < permutation[i])
+                .count()
+        })
+        .sum();
+    swaps % 2 == 1
+}
+
+const fn factorial(n: usize) -> usize {
+    if n == 0 {
+        1
+    } else {
+        n * factorial(n - 1)
+    }
+}
+
+/// A cube state split into independent numbers, with the pieces judged against the centers
+///
+/// Every combination of numbers below the limits is a cube that can be solved
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Coordinates {
+    /// Which color is on each center
+    pub centers: usize,
+    /// Which corner is in each position
+    pub corner_permutation: usize,
+    /// The twist of the first seven corners, the last one follows from them
+    pub corner_twist: usize,
+    /// Which edge is in each position, skipping the one choice that follows from the corners
+    pub edge_permutation: usize,
+    /// The flip of the first eleven edges, the last one follows from them
+    pub edge_flip: usize,
+}
+
+impl Coordinates {
+    pub const CENTERS: usize = factorial(6);
+    pub const CORNER_PERMUTATIONS: usize = factorial(8);
+    pub const CORNER_TWISTS: usize = 3usize.pow(7);
+    pub const EDGE_PERMUTATIONS: usize = factorial(12) / 2;
+    pub const EDGE_FLIPS: usize = 1 << 11;
+
+    pub fn of(cube: &Cube) -> Self {
+        let centers = cube.centers.map(|center| {
+            Side::ALL
+                .iter()
+                .position(|&side| side == center.color)
+                .expect("every color is a side")
+        });
+        let corners = cube.corner_pieces();
+        let edges = cube.edge_pieces();
+        Self {
+            centers: permutation_index(&centers),
+            corner_permutation: permutation_index(&corners.map(|(home, _)| home)),
+            corner_twist: corners[..7]
+                .iter()
+                .rev()
+                .fold(0, |twist, &(_, t)| twist * 3 + usize::from(t)),
+            edge_permutation: permutation_index(&edges.map(|(home, _)| home)) / 2,
+            edge_flip: edges[..11]
+                .iter()
+                .rev()
+                .fold(0, |flip, &(_, f)| flip * 2 + usize::from(f)),
+        }
+    }
+
+    /// Whether every number is below its limit
+    pub const fn is_valid(self) -> bool {
+        self.centers < Self::CENTERS
+            && self.corner_permutation < Self::CORNER_PERMUTATIONS
+            && self.corner_twist < Self::CORNER_TWISTS
+            && self.edge_permutation < Self::EDGE_PERMUTATIONS
+            && self.edge_flip < Self::EDGE_FLIPS
+    }
+
+    /// Builds the cube these numbers describe, which must be [valid](Self::is_valid)
+    pub fn cube(self) -> Cube {
+        let centers = permutation_from_index::<6>(self.centers).map(|i| Side::ALL[i]);
+        let center = |face: Face| centers[face as usize];
+
+        let corner_homes = permutation_from_index::<8>(self.corner_permutation);
+        let mut twists = [0; 8];
+        let mut twist = self.corner_twist;
+        for t in &mut twists[..7] {
+            *t = twist % 3;
+            twist /= 3;
+        }
+        twists[7] = (3 - twists.iter().sum::<usize>() % 3) % 3;
+
+        let edge_homes = [0, 1]
+            .map(|last| permutation_from_index::<12>(self.edge_permutation * 2 + last))
+            .into_iter()
+            .find(|homes| is_odd(homes) == is_odd(&corner_homes))
+            .expect("one of the two orderings has the same parity as the corners");
+        let mut flips = [false; 12];
+        let mut flip = self.edge_flip;
+        for f in &mut flips[..11] {
+            *f = flip % 2 == 1;
+            flip /= 2;
+        }
+        flips[11] = flips.iter().filter(|&&f| f).count() % 2 == 1;
+
+        let mut cube = Cube::new();
+        for (center, color) in cube.centers.iter_mut().zip(centers) {
+            *center = Center { color };
+        }
+        for (position, corner) in cube.corners.iter_mut().enumerate() {
+            let home = CORNER_FACES[corner_homes[position]].map(center);
+            corner.colors = [0, 1, 2].map(|i| home[(i + 3 - twists[position]) % 3]);
+        }
+        for (position, edge) in cube.edges.iter_mut().enumerate() {
+            let [a, b] = EDGE_FACES[edge_homes[position]].map(center);
+            edge.colors = if flips[position] { [b, a] } else { [a, b] };
+        }
+        cube
+    }
+}
+
+/// The number of ways the pieces can be arranged around the centers
+const PIECE_STATES: u128 = Coordinates::CORNER_PERMUTATIONS as u128
+    * Coordinates::CORNER_TWISTS as u128
+    * Coordinates::EDGE_PERMUTATIONS as u128
+    * Coordinates::EDGE_FLIPS as u128;
+
+impl Cube {
+    /// The number of states a cube can reach with moves and rotations, counting each way of
+    /// holding it as its own state
+    pub const STATES: u128 = 24 * PIECE_STATES;
+
+    /// Numbers every state this cube's colors can reach from `0` up to one less than
+    /// [`Cube::STATES`]
+    ///
+    /// Two cubes with the same color scheme get the same number only when they are equal, and
+    /// [`Cube::from_index_with_scheme`] turns the number back into the cube. The solved cube is `0`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+    ///
+    /// assert_eq!(Cube::new().index(), 0);
+    /// assert_eq!(Cube::from_index(cube.index()), Some(cube));
+    /// ```
+    #[must_use]
+    pub fn index(&self) -> u128 {
+        let up = self.center(Face::U);
+        let down = self.center(Face::D);
+        let front = self.center(Face::F);
+        let front_rank = Side::ALL
+            .iter()
+            .take_while(|&&side| side != front)
+            .filter(|&&side| side != up && side != down)
+            .count();
+        let orientation = up as usize * 4 + front_rank;
+
+        let coordinates = Coordinates::of(self);
+        let pieces = [
+            (
+                coordinates.corner_permutation,
+                Coordinates::CORNER_PERMUTATIONS,
+            ),
+            (coordinates.corner_twist, Coordinates::CORNER_TWISTS),
+            (coordinates.edge_permutation, Coordinates::EDGE_PERMUTATIONS),
+            (coordinates.edge_flip, Coordinates::EDGE_FLIPS),
+        ]
+        .into_iter()
+        .fold(0, |index, (value, limit)| {
+            index * limit as u128 + value as u128
+        });
+        orientation as u128 * PIECE_STATES + pieces
+    }
+
+    /// The cube with the default colors that [`Cube::index`] numbers as `index`
+    ///
+    /// Returns `None` when `index` is not below [`Cube::STATES`]
+    #[must_use]
+    pub fn from_index(index: u128) -> Option<Self> {
+        Self::from_index_with_scheme(index, ColorScheme::yellow_top())
+    }
+
+    /// The cube colored with `scheme` that [`Cube::index`] numbers as `index`
+    ///
+    /// Returns `None` when `index` is not below [`Cube::STATES`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{color_scheme::ColorScheme, cube::Cube, moves::Algorithm};
+    ///
+    /// let scheme = ColorScheme::japanese();
+    /// let cube = Cube::new_with_scheme(scheme).apply(Algorithm::from("x R2 F'").unwrap());
+    ///
+    /// assert_eq!(Cube::from_index_with_scheme(cube.index(), scheme), Some(cube));
+    /// assert_eq!(Cube::from_index_with_scheme(Cube::STATES, scheme), None);
+    /// ```
+    #[must_use]
+    pub fn from_index_with_scheme(index: u128, scheme: ColorScheme) -> Option<Self> {
+        if index >= Self::STATES {
+            return None;
+        }
+        let orientation = usize::try_from(index / PIECE_STATES).ok()?;
+        let mut pieces = index % PIECE_STATES;
+        let mut digit = |limit: usize| {
+            let value = usize::try_from(pieces % limit as u128).ok();
+            pieces /= limit as u128;
+            value
+        };
+        let edge_flip = digit(Coordinates::EDGE_FLIPS)?;
+        let edge_permutation = digit(Coordinates::EDGE_PERMUTATIONS)?;
+        let corner_twist = digit(Coordinates::CORNER_TWISTS)?;
+        let corner_permutation = digit(Coordinates::CORNER_PERMUTATIONS)?;
+
+        let up = Side::ALL[orientation / 4];
+        let down = scheme.color(scheme.face_of(up).opposite());
+        let front = Side::ALL
+            .into_iter()
+            .filter(|&side| side != up && side != down)
+            .nth(orientation % 4)?;
+        let held = scheme.held(up, front).ok()?;
+        let centers = Face::ALL.map(|face| held.color(face) as usize);
+
+        Some(
+            Coordinates {
+                centers: permutation_index(&centers),
+                corner_permutation,
+                corner_twist,
+                edge_permutation,
+                edge_flip,
+            }
+            .cube(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod coordinates_tests {
+    use super::*;
+    use crate::moves::Algorithm;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn permutations_round_trip() {
+        for index in 0..factorial(5) {
+            let permutation = permutation_from_index::<5>(index);
+
+            assert_eq!(permutation_index(&permutation), index);
+        }
+    }
+
+    #[test]
+    fn cubes_round_trip() {
+        let moves = Algorithm::from("R U2 F' x 2R Dw' L B2 z' 3F D' y").unwrap();
+        for cube in [
+            Cube::new(),
+            Cube::new().apply(moves.clone()),
+            Cube::new_with_scheme(ColorScheme::japanese()).apply(moves),
+        ] {
+            let coordinates = Coordinates::of(&cube);
+
+            assert!(coordinates.is_valid());
+            assert_eq!(coordinates.cube(), cube);
+        }
+    }
+
+    #[test]
+    fn indices_round_trip() {
+        let moves = Algorithm::from("R U2 F' x 2R Dw' L B2 z' 3F D' y").unwrap();
+        for scheme in [
+            ColorScheme::yellow_top(),
+            ColorScheme::wca(),
+            ColorScheme::japanese(),
+        ] {
+            let cube = Cube::new_with_scheme(scheme).apply(moves.clone());
+
+            assert!(cube.index() < Cube::STATES);
+            assert_eq!(
+                Cube::from_index_with_scheme(cube.index(), scheme),
+                Some(cube)
+            );
+        }
+        assert_eq!(
+            Cube::from_index(Cube::STATES - 1).unwrap().index(),
+            Cube::STATES - 1
+        );
+    }
+
+    #[test]
+    fn every_rotation_has_its_own_index() {
+        let rotations = ["", "y", "y2", "y'", "x", "x2", "x'", "z", "z'"];
+        let indices: HashSet<u128> = rotations
+            .iter()
+            .map(|r| Cube::new().apply(Algorithm::from(r).unwrap()).index())
+            .collect();
+
+        assert_eq!(indices.len(), rotations.len());
+        assert!(indices.iter().all(|&index| index % PIECE_STATES == 0));
+    }
+
+    #[test]
+    fn cubes_are_hash_map_keys() {
+        let mut seen = HashMap::new();
+        let mut cube = Cube::new();
+        for i in 0..6 {
+            seen.insert(cube.clone(), i);
+            cube = cube.apply(Algorithm::from("R U R' U'").unwrap());
+        }
+
+        assert_eq!(seen.len(), 6);
+        assert_eq!(seen.get(&Cube::new()), Some(&0));
+    }
+}
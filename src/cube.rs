@@ -1,9 +1,15 @@
+//! A model of the 3x3 cube that algorithms can be performed on
 use std::fmt::Display;
 
-use crate::moves::{Algorithm, FaceTurn, Move};
+use crate::{
+    color_scheme::ColorScheme,
+    moves::{Algorithm, FaceTurn, LayerTurn, Move, Rotation},
+    terminal::{self, Style},
+};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Side {
+/// The color of a sticker, named after the side of a solved cube it belongs on
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Side {
     Yellow,
     White,
     Red,
@@ -12,16 +18,202 @@ enum Side {
     Green,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl Side {
+    pub(crate) const ALL: [Self; 6] = [
+        Self::Yellow,
+        Self::White,
+        Self::Red,
+        Self::Orange,
+        Self::Blue,
+        Self::Green,
+    ];
+}
+
+/// The four places an F2L pair can go, named from the front with the cross on the bottom
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Slot {
+    FR,
+    FL,
+    BR,
+    BL,
+}
+
+impl Slot {
+    pub(crate) const ALL: [Self; 4] = [Self::FR, Self::FL, Self::BR, Self::BL];
+
+    /// The corner position of this slot
+    pub(crate) const fn corner(self) -> usize {
+        match self {
+            Self::FR => 3,
+            Self::FL => 4,
+            Self::BR => 2,
+            Self::BL => 5,
+        }
+    }
+
+    /// The edge position of this slot
+    pub(crate) const fn edge(self) -> usize {
+        match self {
+            Self::FR => 5,
+            Self::FL => 6,
+            Self::BR => 4,
+            Self::BL => 7,
+        }
+    }
+}
+
+/// The six faces of the cube
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Face {
+    U,
+    D,
+    F,
+    B,
+    L,
+    R,
+}
+
+/// The faces each corner position touches, listed clockwise starting from the U or D face
+pub(crate) const CORNER_FACES: [[Face; 3]; 8] = [
+    [Face::U, Face::R, Face::F],
+    [Face::U, Face::B, Face::R],
+    [Face::D, Face::R, Face::B],
+    [Face::D, Face::F, Face::R],
+    [Face::D, Face::L, Face::F],
+    [Face::D, Face::B, Face::L],
+    [Face::U, Face::L, Face::B],
+    [Face::U, Face::F, Face::L],
+];
+
+/// The faces each edge position touches, in the order [`Edge`] stores its colors
+pub(crate) const EDGE_FACES: [[Face; 2]; 12] = [
+    [Face::U, Face::R],
+    [Face::U, Face::F],
+    [Face::U, Face::L],
+    [Face::U, Face::B],
+    [Face::B, Face::R],
+    [Face::F, Face::R],
+    [Face::F, Face::L],
+    [Face::B, Face::L],
+    [Face::D, Face::R],
+    [Face::D, Face::F],
+    [Face::D, Face::L],
+    [Face::D, Face::B],
+];
+
+impl Face {
+    pub(crate) const ALL: [Self; 6] = [Self::U, Self::D, Self::F, Self::B, Self::L, Self::R];
+
+    /// Where this face ends up after `rotation`
+    pub(crate) fn rotate(self, rotation: Rotation) -> Self {
+        let (Rotation::X(n) | Rotation::Y(n) | Rotation::Z(n)) = rotation;
+        (0..n).fold(self, |face, _| face.quarter_rotate(rotation))
+    }
+
+    const fn quarter_rotate(self, rotation: Rotation) -> Self {
+        match (rotation, self) {
+            (Rotation::X(_), Self::F) | (Rotation::Z(_), Self::L) => Self::U,
+            (Rotation::X(_), Self::U) | (Rotation::Y(_), Self::L) => Self::B,
+            (Rotation::X(_), Self::B) | (Rotation::Z(_), Self::R) => Self::D,
+            (Rotation::X(_), Self::D) | (Rotation::Y(_), Self::R) => Self::F,
+            (Rotation::Y(_), Self::F) | (Rotation::Z(_), Self::D) => Self::L,
+            (Rotation::Y(_), Self::B) | (Rotation::Z(_), Self::U) => Self::R,
+            (_, face) => face,
+        }
+    }
+
+    /// The direction pointing out of this face, with x going to R, y going to U, and z going to F
+    pub(crate) const fn normal(self) -> [i32; 3] {
+        match self {
+            Self::U => [0, 1, 0],
+            Self::D => [0, -1, 0],
+            Self::F => [0, 0, 1],
+            Self::B => [0, 0, -1],
+            Self::L => [-1, 0, 0],
+            Self::R => [1, 0, 0],
+        }
+    }
+
+    /// The face pointing in the direction `normal`
+    pub(crate) fn from_normal(normal: [i32; 3]) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|face| face.normal() == normal)
+            .expect("every axis direction is a face")
+    }
+
+    /// The face on the other side of the cube
+    pub(crate) const fn opposite(self) -> Self {
+        match self {
+            Self::U => Self::D,
+            Self::D => Self::U,
+            Self::F => Self::B,
+            Self::B => Self::F,
+            Self::L => Self::R,
+            Self::R => Self::L,
+        }
+    }
+
+    /// The rotation that turns the whole cube the same way as turning this face by `amount`
+    pub(crate) const fn rotation(self, amount: u8) -> Rotation {
+        match self {
+            Self::R => Rotation::X(amount),
+            Self::L => Rotation::X((amount * 3) % 4),
+            Self::U => Rotation::Y(amount),
+            Self::D => Rotation::Y((amount * 3) % 4),
+            Self::F => Rotation::Z(amount),
+            Self::B => Rotation::Z((amount * 3) % 4),
+        }
+    }
+
+    /// The face turn of this face by `amount` quarter turns
+    pub(crate) const fn turn(self, amount: u8) -> FaceTurn {
+        match self {
+            Self::U => FaceTurn::U(amount),
+            Self::D => FaceTurn::D(amount),
+            Self::F => FaceTurn::F(amount),
+            Self::B => FaceTurn::B(amount),
+            Self::L => FaceTurn::L(amount),
+            Self::R => FaceTurn::R(amount),
+        }
+    }
+}
+
+impl FaceTurn {
+    /// The face this turn moves, and by how many quarter turns
+    pub(crate) const fn face(self) -> (Face, u8) {
+        match self {
+            Self::U(t) => (Face::U, t),
+            Self::D(t) => (Face::D, t),
+            Self::F(t) => (Face::F, t),
+            Self::B(t) => (Face::B, t),
+            Self::L(t) => (Face::L, t),
+            Self::R(t) => (Face::R, t),
+        }
+    }
+}
+
+/// The state of a 3x3 cube, which [`Algorithm`]s can be applied to
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm};
+///
+/// let cube = Cube::new().apply(Algorithm::sexy());
+///
+/// assert_ne!(cube, Cube::new());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
 pub struct Cube {
-    corners: [Corner; 8],
-    edges: [Edge; 12],
-    centers: [Center; 6],
+    pub(crate) corners: [Corner; 8],
+    pub(crate) edges: [Edge; 12],
+    pub(crate) centers: [Center; 6],
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Corner {
-    colors: [Side; 3],
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub(crate) struct Corner {
+    pub(crate) colors: [Side; 3],
 }
 
 impl Corner {
@@ -32,9 +224,9 @@ impl Corner {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Edge {
-    colors: [Side; 2],
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub(crate) struct Edge {
+    pub(crate) colors: [Side; 2],
 }
 
 impl Edge {
@@ -45,12 +237,14 @@ impl Edge {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Center {
-    color: Side,
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub(crate) struct Center {
+    pub(crate) color: Side,
 }
 
 impl Cube {
+    /// Creates a solved cube, with yellow on top and red in front
+    #[must_use]
     pub const fn new() -> Self {
         Self {
             corners: [
@@ -132,6 +326,33 @@ impl Cube {
         }
     }
 
+    /// Creates a solved cube, colored and held the way `scheme` says
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{color_scheme::ColorScheme, cube::Cube};
+    ///
+    /// assert_eq!(Cube::new_with_scheme(ColorScheme::yellow_top()), Cube::new());
+    /// assert_ne!(Cube::new_with_scheme(ColorScheme::wca()), Cube::new());
+    /// ```
+    #[must_use]
+    pub fn new_with_scheme(scheme: ColorScheme) -> Self {
+        let mut cube = Self::new();
+        for (corner, faces) in cube.corners.iter_mut().zip(CORNER_FACES) {
+            corner.colors = faces.map(|face| scheme.color(face));
+        }
+        for (edge, faces) in cube.edges.iter_mut().zip(EDGE_FACES) {
+            edge.colors = faces.map(|face| scheme.color(face));
+        }
+        for face in Face::ALL {
+            cube.centers[face as usize].color = scheme.color(face);
+        }
+        cube
+    }
+
+    /// Performs every move of an [`Algorithm`], in order, on a copy of this cube
+    #[must_use]
     pub fn apply(&self, moves: Algorithm) -> Self {
         let mut cube = self.clone();
         for m in moves.moves {
@@ -228,9 +449,223 @@ impl Cube {
                 cube = cube.apply_move(Move::FaceTurn(FaceTurn::R(1)));
                 cube = cube.apply_move(Move::FaceTurn(FaceTurn::R(n - 1)));
             }
+            Move::Rotation(rotation) => {
+                cube = self.rotate(rotation);
+            }
+            Move::Layer(layer) => {
+                cube = self.turn_layers(layer);
+            }
+            Move::Slice(slice) => {
+                cube = self.turn_layers(slice.layer());
+            }
+        }
+        cube
+    }
+
+    fn rotate(&self, rotation: Rotation) -> Self {
+        self.reorient(|face| face.rotate(rotation), |_| true)
+    }
+
+    /// Turns the layers of a [`LayerTurn`], where the 3rd layer is the opposite face
+    ///
+    /// Wide turns deeper than the cube turn the whole cube, and turns of single layers deeper
+    /// than the cube do nothing
+    fn turn_layers(&self, layer: LayerTurn) -> Self {
+        let (face, amount) = layer.turn.face();
+        let rotation = face.rotation(amount);
+        let turned = |faces: &[Face]| {
+            let depth = if faces.contains(&face) {
+                1
+            } else if faces.contains(&face.opposite()) {
+                3
+            } else {
+                2
+            };
+            if layer.wide {
+                depth <= layer.depth
+            } else {
+                depth == layer.depth
+            }
         };
+        self.reorient(|face| face.rotate(rotation), turned)
+    }
+
+    /// Moves every piece whose faces are picked by `moving` to the position its faces are sent to by `to`
+    fn reorient(&self, to: impl Fn(Face) -> Face, moving: impl Fn(&[Face]) -> bool) -> Self {
+        let mut cube = self.clone();
+        for (from, corner) in self.corners.iter().enumerate() {
+            if !moving(&CORNER_FACES[from]) {
+                continue;
+            }
+            let faces = CORNER_FACES[from].map(&to);
+            let (position, shift) = (0..8)
+                .flat_map(|position| (0..3).map(move |shift| (position, shift)))
+                .find(|&(position, shift)| {
+                    (0..3).all(|i| CORNER_FACES[position][i] == faces[(i + shift) % 3])
+                })
+                .expect("rotations keep corners as corners");
+            cube.corners[position].colors = [0, 1, 2].map(|i| corner.colors[(i + shift) % 3]);
+        }
+        for (from, edge) in self.edges.iter().enumerate() {
+            if !moving(&EDGE_FACES[from]) {
+                continue;
+            }
+            let faces = EDGE_FACES[from].map(&to);
+            let position = EDGE_FACES
+                .iter()
+                .position(|f| f.contains(&faces[0]) && f.contains(&faces[1]))
+                .expect("rotations keep edges as edges");
+            cube.edges[position] = if EDGE_FACES[position][0] == faces[0] {
+                *edge
+            } else {
+                edge.flip()
+            };
+        }
+        for face in Face::ALL.into_iter().filter(|face| moving(&[*face])) {
+            cube.centers[to(face) as usize] = self.centers[face as usize];
+        }
         cube
     }
+
+    /// The color of the center on `face`
+    pub(crate) const fn center(&self, face: Face) -> Side {
+        self.centers[face as usize].color
+    }
+
+    /// The face whose center has the given color
+    pub(crate) fn face_of(&self, color: Side) -> Face {
+        Face::ALL
+            .into_iter()
+            .find(|&face| self.center(face) == color)
+            .expect("every color has a center")
+    }
+
+    /// Finds which edge is in each position, and whether it is flipped, judged against the centers
+    ///
+    /// The edge that belongs in position `i` is called edge `i`, and it is flipped when its
+    /// first color is not on the first face of the position it is in
+    pub(crate) fn edge_pieces(&self) -> [(usize, bool); 12] {
+        self.edges.map(|edge| {
+            let faces = edge.colors.map(|color| self.face_of(color));
+            let home = EDGE_FACES
+                .iter()
+                .position(|f| f.contains(&faces[0]) && f.contains(&faces[1]))
+                .expect("edges have two adjacent colors");
+            (home, EDGE_FACES[home][0] != faces[0])
+        })
+    }
+
+    /// Finds which corner is in each position, and how far it is twisted, judged against the centers
+    ///
+    /// The corner that belongs in position `i` is called corner `i`, and its twist is which of
+    /// the faces of its position has the corner's U or D color on it
+    pub(crate) fn corner_pieces(&self) -> [(usize, u8); 8] {
+        self.corners.map(|corner| {
+            let faces = corner.colors.map(|color| self.face_of(color));
+            let home = CORNER_FACES
+                .iter()
+                .position(|f| faces.iter().all(|face| f.contains(face)))
+                .expect("corners have three adjacent colors");
+            let twist = faces
+                .iter()
+                .position(|&face| face == CORNER_FACES[home][0])
+                .expect("the corner is made of its home faces");
+            (home, u8::try_from(twist).expect("corners have three faces"))
+        })
+    }
+
+    /// Whether the edge in `position` matches the centers around it
+    fn edge_solved(&self, position: usize) -> bool {
+        let [a, b] = EDGE_FACES[position];
+        self.edges[position].colors == [self.center(a), self.center(b)]
+    }
+
+    /// Whether the corner in `position` matches the centers around it
+    fn corner_solved(&self, position: usize) -> bool {
+        self.corners[position].colors == CORNER_FACES[position].map(|face| self.center(face))
+    }
+
+    /// Determines if the corner and edge of the F2L pair in `slot` both match their centers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::{Cube, Slot}, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("R U R'").unwrap());
+    ///
+    /// assert!(!cube.is_pair_solved(Slot::FR));
+    /// assert!(cube.is_pair_solved(Slot::BL));
+    /// ```
+    #[must_use]
+    pub fn is_pair_solved(&self, slot: Slot) -> bool {
+        self.corner_solved(slot.corner()) && self.edge_solved(slot.edge())
+    }
+
+    /// Determines if the four edges around the `color` center all match their centers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::{Cube, Side}, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("R U R'").unwrap());
+    ///
+    /// assert!(cube.is_cross_solved(Side::White));
+    /// assert!(!cube.is_cross_solved(Side::Yellow));
+    /// ```
+    #[must_use]
+    pub fn is_cross_solved(&self, color: Side) -> bool {
+        let face = self.face_of(color);
+        (0..12)
+            .filter(|&position| EDGE_FACES[position].contains(&face))
+            .all(|position| self.edge_solved(position))
+    }
+
+    /// Determines whether the edge in each position is oriented relative to F and B, judged
+    /// against the centers
+    ///
+    /// An edge is oriented when it can be solved without quarter turns of F or B, which flip every
+    /// edge they move. The positions are in the order UR, UF, UL, UB, BR, FR, FL, BL, DR, DF, DL,
+    /// DB
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("F").unwrap());
+    ///
+    /// assert_eq!(
+    ///     cube.edge_orientation(),
+    ///     [true, false, true, true, true, false, false, true, true, false, true, true]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn edge_orientation(&self) -> [bool; 12] {
+        self.edge_pieces().map(|(_, flipped)| !flipped)
+    }
+
+    /// Determines if every edge is oriented relative to F and B
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// assert!(Cube::new().apply(Algorithm::from("R U2 F2 L'").unwrap()).is_eo_solved());
+    /// assert!(!Cube::new().apply(Algorithm::from("R U2 F L'").unwrap()).is_eo_solved());
+    /// ```
+    #[must_use]
+    pub fn is_eo_solved(&self) -> bool {
+        !self.edge_orientation().contains(&false)
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Display for Side {
@@ -249,72 +684,12 @@ impl Display for Side {
 
 impl Display for Cube {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "⬛⬛⬛{}{}{}⬛⬛⬛⬛⬛⬛
-⬛⬛⬛{}{}{}⬛⬛⬛⬛⬛⬛
-⬛⬛⬛{}{}{}⬛⬛⬛⬛⬛⬛
-{}{}{}{}{}{}{}{}{}{}{}{}
-{}{}{}{}{}{}{}{}{}{}{}{}
-{}{}{}{}{}{}{}{}{}{}{}{}
-⬛⬛⬛{}{}{}⬛⬛⬛⬛⬛⬛
-⬛⬛⬛{}{}{}⬛⬛⬛⬛⬛⬛
-⬛⬛⬛{}{}{}⬛⬛⬛⬛⬛⬛",
-            self.corners[6].colors[0],
-            self.edges[3].colors[0],
-            self.corners[1].colors[0],
-            self.edges[2].colors[0],
-            self.centers[0].color,
-            self.edges[0].colors[0],
-            self.corners[7].colors[0],
-            self.edges[1].colors[0],
-            self.corners[0].colors[0],
-            self.corners[6].colors[1],
-            self.edges[2].colors[1],
-            self.corners[7].colors[2],
-            self.corners[7].colors[1],
-            self.edges[1].colors[1],
-            self.corners[0].colors[2],
-            self.corners[0].colors[1],
-            self.edges[0].colors[1],
-            self.corners[1].colors[2],
-            self.corners[1].colors[1],
-            self.edges[3].colors[1],
-            self.corners[6].colors[2],
-            self.edges[7].colors[1],
-            self.centers[4].color,
-            self.edges[6].colors[1],
-            self.edges[6].colors[0],
-            self.centers[2].color,
-            self.edges[5].colors[0],
-            self.edges[5].colors[1],
-            self.centers[5].color,
-            self.edges[4].colors[1],
-            self.edges[4].colors[0],
-            self.centers[3].color,
-            self.edges[7].colors[0],
-            self.corners[5].colors[2],
-            self.edges[10].colors[1],
-            self.corners[4].colors[1],
-            self.corners[4].colors[2],
-            self.edges[9].colors[1],
-            self.corners[3].colors[1],
-            self.corners[3].colors[2],
-            self.edges[8].colors[1],
-            self.corners[2].colors[1],
-            self.corners[2].colors[2],
-            self.edges[11].colors[1],
-            self.corners[5].colors[1],
-            self.corners[4].colors[0],
-            self.edges[9].colors[0],
-            self.corners[3].colors[0],
-            self.edges[10].colors[0],
-            self.centers[1].color,
-            self.edges[8].colors[0],
-            self.corners[5].colors[0],
-            self.edges[11].colors[0],
-            self.corners[2].colors[0],
-        )
+        let style = if f.alternate() {
+            Style::Ansi
+        } else {
+            Style::Emoji
+        };
+        write!(f, "{}", terminal::net(self, style))
     }
 }
 
@@ -452,6 +827,176 @@ mod cube_tests {
         assert_str_eq!(actual, expected);
     }
 
+    #[test]
+    fn x_rotation() {
+        let cube = Cube::new();
+
+        let cube = cube.apply(Algorithm::from("x").unwrap());
+        let actual = format!("{cube}");
+        let expected = "⬛⬛⬛🟥🟥🟥⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟥🟥🟥⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟥🟥🟥⬛⬛⬛⬛⬛⬛
+🟦🟦🟦⬜⬜⬜🟩🟩🟩🟨🟨🟨
+🟦🟦🟦⬜⬜⬜🟩🟩🟩🟨🟨🟨
+🟦🟦🟦⬜⬜⬜🟩🟩🟩🟨🟨🟨
+⬛⬛⬛🟧🟧🟧⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟧🟧🟧⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟧🟧🟧⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn y_then_r_is_the_same_as_b_then_y() {
+        let actual = Cube::new().apply(Algorithm::from("y R").unwrap());
+        let expected = Cube::new().apply(Algorithm::from("B y").unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn z_then_u_is_the_same_as_l_then_z() {
+        let actual = Cube::new().apply(Algorithm::from("z U").unwrap());
+        let expected = Cube::new().apply(Algorithm::from("L z").unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn x_then_u_is_the_same_as_f_then_x() {
+        let actual = Cube::new().apply(Algorithm::from("x U R").unwrap());
+        let expected = Cube::new().apply(Algorithm::from("F R x").unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn second_layer_of_r() {
+        let cube = Cube::new().apply(Algorithm::from("2R").unwrap());
+        let actual = format!("{cube}");
+        let expected = "⬛⬛⬛🟨🟥🟨⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟨🟥🟨⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟨🟥🟨⬛⬛⬛⬛⬛⬛
+🟦🟦🟦🟥⬜🟥🟩🟩🟩🟧🟨🟧
+🟦🟦🟦🟥⬜🟥🟩🟩🟩🟧🟨🟧
+🟦🟦🟦🟥⬜🟥🟩🟩🟩🟧🟨🟧
+⬛⬛⬛⬜🟧⬜⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬜🟧⬜⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬜🟧⬜⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn layer_turns_match_face_turns_and_rotations() {
+        let cube = Cube::new();
+
+        assert_eq!(
+            cube.apply(Algorithm::from("Uw").unwrap()),
+            cube.apply(Algorithm::from("D y").unwrap())
+        );
+        assert_eq!(
+            cube.apply(Algorithm::from("3Fw'").unwrap()),
+            cube.apply(Algorithm::from("z'").unwrap())
+        );
+        assert_eq!(
+            cube.apply(Algorithm::from("3L2").unwrap()),
+            cube.apply(Algorithm::from("R2").unwrap())
+        );
+        assert_eq!(
+            cube.apply(Algorithm::from("1B").unwrap()),
+            cube.apply(Algorithm::from("B").unwrap())
+        );
+    }
+
+    #[test]
+    fn slice_turns_match_layer_turns() {
+        let cube = Cube::new();
+
+        assert_eq!(
+            cube.apply(Algorithm::from("M").unwrap()),
+            cube.apply(Algorithm::from("2L").unwrap())
+        );
+        assert_eq!(
+            cube.apply(Algorithm::from("r").unwrap()),
+            cube.apply(Algorithm::from("R M'").unwrap())
+        );
+        assert_eq!(
+            cube.apply(Algorithm::from("E").unwrap()),
+            cube.apply(Algorithm::from("U D' y'").unwrap())
+        );
+        assert_eq!(
+            cube.apply(Algorithm::from("S'").unwrap()),
+            cube.apply(Algorithm::from("F B' z'").unwrap())
+        );
+    }
+
+    #[test]
+    fn cross_survives_moves_that_put_its_edges_back() {
+        assert!(Cube::new()
+            .apply(Algorithm::from("U R U R'").unwrap())
+            .is_cross_solved(Side::White));
+        assert!(Cube::new()
+            .apply(Algorithm::from("D2 R' D R").unwrap())
+            .is_cross_solved(Side::Yellow));
+    }
+
+    #[test]
+    fn cross_is_broken_by_turning_its_face_or_a_side() {
+        let cube = Cube::new().apply(Algorithm::from("D2 U R U'").unwrap());
+
+        assert!(!cube.is_cross_solved(Side::White));
+        assert!(!cube.is_cross_solved(Side::Yellow));
+        assert!(!Cube::new()
+            .apply(Algorithm::from("D").unwrap())
+            .is_cross_solved(Side::White));
+    }
+
+    #[test]
+    fn pairs_are_solved_until_their_slot_is_turned() {
+        let cube = Cube::new().apply(Algorithm::from("F U F'").unwrap());
+
+        assert!(cube.is_pair_solved(Slot::FR));
+        assert!(!cube.is_pair_solved(Slot::FL));
+        assert!(cube.is_pair_solved(Slot::BR));
+        assert!(cube.is_pair_solved(Slot::BL));
+    }
+
+    #[test]
+    fn edge_orientation_follows_the_centers() {
+        let turned = Cube::new().apply(Algorithm::from("F").unwrap());
+
+        assert_eq!(turned.edge_orientation().iter().filter(|&&o| !o).count(), 4);
+        assert!(turned.apply(Algorithm::from("y").unwrap()).is_eo_solved());
+        assert!(!turned.apply(Algorithm::from("z").unwrap()).is_eo_solved());
+    }
+
+    #[test]
+    fn wca_scheme_display() {
+        let cube = Cube::new_with_scheme(ColorScheme::wca());
+        let actual = format!("{}", cube.apply(Algorithm::from("R").unwrap()));
+        let expected = "⬛⬛⬛⬜⬜🟩⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬜⬜🟩⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬜⬜🟩⬛⬛⬛⬛⬛⬛
+🟧🟧🟧🟩🟩🟨🟥🟥🟥⬜🟦🟦
+🟧🟧🟧🟩🟩🟨🟥🟥🟥⬜🟦🟦
+🟧🟧🟧🟩🟩🟨🟥🟥🟥⬜🟦🟦
+⬛⬛⬛🟨🟨🟦⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟨🟨🟦⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🟨🟨🟦⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn cross_follows_the_scheme() {
+        let cube =
+            Cube::new_with_scheme(ColorScheme::japanese()).apply(Algorithm::from("U").unwrap());
+
+        assert!(cube.is_cross_solved(Side::Blue));
+        assert!(!cube.is_cross_solved(Side::Green));
+    }
+
     #[test]
     fn rotate_corner() {
         let corner = Corner {
@@ -0,0 +1,204 @@
+//! A model of the 2x2 cube, which is the same as the corners of a 3x3
+use std::fmt::Display;
+
+use rand::Rng;
+
+use crate::{
+    color_scheme::ColorScheme,
+    cube::{Corner, Cube, Face, CORNER_FACES},
+    moves::{Algorithm, LayerTurn, Move},
+    terminal::{self, Style},
+};
+
+/// The state of a 2x2 cube, which [`Algorithm`]s can be applied to
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube2::Cube2, moves::Algorithm};
+///
+/// let cube = Cube2::new().apply(Algorithm::from("R U R' U'").unwrap());
+///
+/// assert!(!cube.is_solved());
+/// assert!(cube.apply(Algorithm::from("U R U' R'").unwrap()).is_solved());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct Cube2 {
+    pub(crate) corners: [Corner; 8],
+}
+
+impl Cube2 {
+    /// Creates a solved cube, with yellow on top and red in front
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            corners: Cube::new().corners,
+        }
+    }
+
+    /// Creates a solved cube, colored and held the way `scheme` says
+    #[must_use]
+    pub fn new_with_scheme(scheme: ColorScheme) -> Self {
+        Self {
+            corners: Cube::new_with_scheme(scheme).corners,
+        }
+    }
+
+    /// Creates a cube where every reachable state is equally likely, with the DBL corner kept in place
+    #[must_use]
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let solved = Self::new().corners;
+        let mut pieces = [0, 1, 2, 3, 4, 6, 7];
+        for i in (1..pieces.len()).rev() {
+            pieces.swap(i, rng.gen_range(0..=i));
+        }
+        let mut twists: [u8; 7] = [0; 7];
+        for twist in &mut twists[1..] {
+            *twist = rng.gen_range(0..3);
+        }
+        twists[0] = (3 - twists[1..].iter().sum::<u8>() % 3) % 3;
+
+        let mut corners = solved;
+        let positions = [0, 1, 2, 3, 4, 6, 7];
+        for ((position, piece), twist) in positions.into_iter().zip(pieces).zip(twists) {
+            let colors = solved[piece].colors;
+            corners[position].colors = [0, 1, 2].map(|i| colors[(i + 3 - usize::from(twist)) % 3]);
+        }
+        Self { corners }
+    }
+
+    /// Performs every move of an [`Algorithm`], in order, on a copy of this cube
+    ///
+    /// The 2nd layer of a 2x2 is the opposite face, so a wide turn like Rw turns the whole cube
+    #[must_use]
+    pub fn apply(&self, moves: Algorithm) -> Self {
+        let moves: Vec<Move> = moves
+            .into_iter()
+            .map(|m| match m {
+                Move::Layer(layer) if layer.depth >= 2 && (layer.wide || layer.depth == 2) => {
+                    Move::Layer(LayerTurn { depth: 3, ..layer })
+                }
+                Move::Layer(layer) if layer.depth >= 2 => {
+                    Move::Layer(LayerTurn { depth: 4, ..layer })
+                }
+                m => m,
+            })
+            .collect();
+        let cube = Cube {
+            corners: self.corners,
+            ..Cube::new()
+        }
+        .apply(moves.into());
+        Self {
+            corners: cube.corners,
+        }
+    }
+
+    /// Determines if every face is a single color, no matter which way the cube is held
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube2::Cube2, moves::Algorithm};
+    ///
+    /// let cube = Cube2::new().apply(Algorithm::from("R L'").unwrap());
+    ///
+    /// assert!(cube.is_solved());
+    /// ```
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        Face::ALL.into_iter().all(|face| {
+            let mut stickers = self
+                .corners
+                .iter()
+                .zip(CORNER_FACES)
+                .filter_map(|(c, faces)| {
+                    faces.iter().position(|&f| f == face).map(|i| c.colors[i])
+                });
+            let first = stickers.next();
+            stickers.all(|color| Some(color) == first)
+        })
+    }
+}
+
+impl Default for Cube2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for Cube2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let style = if f.alternate() {
+            Style::Ansi
+        } else {
+            Style::Emoji
+        };
+        write!(f, "{}", terminal::net(self, style))
+    }
+}
+
+#[cfg(test)]
+mod cube2_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn basic_cube_display() {
+        let cube = Cube2::new();
+        let actual = format!("{cube}");
+        let expected = "⬛⬛🟨🟨⬛⬛⬛⬛
+⬛⬛🟨🟨⬛⬛⬛⬛
+🟦🟦🟥🟥🟩🟩🟧🟧
+🟦🟦🟥🟥🟩🟩🟧🟧
+⬛⬛⬜⬜⬛⬛⬛⬛
+⬛⬛⬜⬜⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn r_turn() {
+        let cube = Cube2::new().apply(Algorithm::from("R").unwrap());
+        let actual = format!("{cube}");
+        let expected = "⬛⬛🟨🟥⬛⬛⬛⬛
+⬛⬛🟨🟥⬛⬛⬛⬛
+🟦🟦🟥⬜🟩🟩🟨🟧
+🟦🟦🟥⬜🟩🟩🟨🟧
+⬛⬛⬜🟧⬛⬛⬛⬛
+⬛⬛⬜🟧⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rotations_are_still_solved() {
+        let cube = Cube2::new().apply(Algorithm::from("x y2 z'").unwrap());
+
+        assert_ne!(cube, Cube2::new());
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn layer_turns_count_two_layers() {
+        let cube = Cube2::new();
+
+        assert_eq!(
+            cube.apply(Algorithm::from("2R").unwrap()),
+            cube.apply(Algorithm::from("L'").unwrap())
+        );
+        assert!(cube.apply(Algorithm::from("Rw 3Uw'").unwrap()).is_solved());
+        assert_eq!(cube.apply(Algorithm::from("3F").unwrap()), cube);
+    }
+
+    #[test]
+    fn random_cubes_are_not_solved() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let cube = Cube2::random(&mut rng);
+
+        assert!(!cube.is_solved());
+        assert_eq!(cube.corners[5], Cube2::new().corners[5]);
+    }
+}
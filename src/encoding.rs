@@ -0,0 +1,417 @@
+//! A compact binary form for algorithms and cube states, for storing lots of them
+//!
+//! Every encoding starts with a byte for its version, which is [`VERSION`] for everything written
+//! by this version of the crate, so that old data can still be read if the format changes.
+//! Version 1 had no slice turns, and everything it wrote is read the same way by version 2
+//!
+//! - An [`Algorithm`] is the number of moves, then 5 bits for each face turn or rotation. Layer
+//!   turns take 19 bits, since they also need their depth, and slice turns take 10
+//! - A [`Cube`] is always 11 bytes: the version, then the state packed into 10 bytes as
+//!   coordinates, which are which color each center is, and where each piece is and how it's
+//!   turned compared to the centers
+//!
+//! Only 24 arrangements of the centers can be reached by turning a cube, and storing just those
+//! would fit the state in 9 bytes. Storing all 720 costs the tenth byte, but keeps the color
+//! scheme, so cubes from [`Cube::new_with_scheme`] read back with the colors they were written with
+use thiserror::Error;
+
+use crate::{
+    coordinates::Coordinates,
+    cube::{Cube, Face},
+    moves::{Algorithm, FaceTurn, LayerTurn, Move, Rotation, SliceTurn},
+};
+
+/// The version of the encoding written by this version of the crate
+pub const VERSION: u8 = 2;
+
+/// The number of bytes in an encoded [`Cube`]
+pub const CUBE_BYTES: usize = 11;
+
+/// The code that is followed by the rest of a layer turn
+const LAYER: u8 = 27;
+
+/// The code that is followed by the face turn of L, D, or F that a slice turn follows
+const SLICE: u8 = 28;
+
+/// Occurs when bytes can't be decoded
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("version {0} of the encoding is not known")]
+    UnknownVersion(u8),
+    #[error("the bytes ended before everything was read")]
+    TooShort,
+    #[error("there are bytes left over after everything was read")]
+    TooLong,
+    #[error("{0} is not the code of a move")]
+    UnknownMove(u8),
+    #[error("the bytes are not a cube state")]
+    NotACube,
+}
+
+/// Writes numbers into bytes a few bits at a time, starting from the highest bit of each byte
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    used: u32,
+}
+
+impl BitWriter {
+    /// Writes a number of up to 8 bits
+    fn write(&mut self, value: u8, bits: u32) {
+        for bit in (0..bits).rev() {
+            if self.used.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            let last = self.bytes.last_mut().expect("a byte was just added");
+            *last |= u8::from((value >> bit) & 1 == 1) << (7 - self.used % 8);
+            self.used += 1;
+        }
+    }
+}
+
+/// Reads numbers written by [`BitWriter`]
+#[derive(Debug)]
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    used: usize,
+}
+
+impl BitReader<'_> {
+    /// Reads a number of up to 8 bits
+    fn read(&mut self, bits: u32) -> Result<u8, DecodeError> {
+        (0..bits).try_fold(0, |value, _| {
+            let byte = self.bytes.get(self.used / 8).ok_or(DecodeError::TooShort)?;
+            let bit = (byte >> (7 - self.used % 8)) & 1;
+            self.used += 1;
+            Ok(value << 1 | bit)
+        })
+    }
+}
+
+/// Skips the version, which can be [`VERSION`] or any earlier one
+const fn version(bytes: &[u8]) -> Result<&[u8], DecodeError> {
+    match bytes.split_first() {
+        Some((&(1..=VERSION), rest)) => Ok(rest),
+        Some((&version, _)) => Err(DecodeError::UnknownVersion(version)),
+        None => Err(DecodeError::TooShort),
+    }
+}
+
+/// The 5 bit code of a face turn, or nothing for turns that do nothing
+fn face_turn_code(turn: FaceTurn) -> Option<u8> {
+    let (face, amount) = turn.face();
+    let amount = amount % 4;
+    (amount != 0).then(|| face as u8 * 3 + amount - 1)
+}
+
+fn face_turn(code: u8) -> Option<FaceTurn> {
+    let face = *Face::ALL.get(usize::from(code / 3))?;
+    Some(face.turn(code % 3 + 1))
+}
+
+const fn rotation_code(rotation: Rotation) -> Option<u8> {
+    let (axis, amount) = match rotation {
+        Rotation::X(t) => (0, t),
+        Rotation::Y(t) => (1, t),
+        Rotation::Z(t) => (2, t),
+    };
+    match amount % 4 {
+        0 => None,
+        amount => Some(18 + axis * 3 + amount - 1),
+    }
+}
+
+const fn rotation(code: u8) -> Option<Rotation> {
+    let amount = (code - 18) % 3 + 1;
+    match (code - 18) / 3 {
+        0 => Some(Rotation::X(amount)),
+        1 => Some(Rotation::Y(amount)),
+        2 => Some(Rotation::Z(amount)),
+        _ => None,
+    }
+}
+
+impl Algorithm {
+    /// Packs the moves into bytes, as described in [the module docs](crate::encoding)
+    ///
+    /// Turns of a whole number of full turns, like `U(0)` or `U(4)`, are left out, since they
+    /// don't do anything
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::Algorithm;
+    ///
+    /// let alg = Algorithm::from("R U R' U' R' F R2 U' R' U' R U R' F'").unwrap();
+    /// let bytes = alg.to_bytes();
+    ///
+    /// assert_eq!(bytes.len(), 11);
+    /// assert_eq!(Algorithm::from_bytes(&bytes), Ok(alg));
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        let codes: Vec<(u8, Option<u8>, Option<LayerTurn>)> = self
+            .moves
+            .iter()
+            .filter_map(|&m| match m {
+                Move::FaceTurn(turn) => face_turn_code(turn).map(|code| (code, None, None)),
+                Move::Rotation(rotation) => rotation_code(rotation).map(|code| (code, None, None)),
+                Move::Layer(layer) => {
+                    face_turn_code(layer.turn).map(|turn| (LAYER, Some(turn), Some(layer)))
+                }
+                Move::Slice(slice) => {
+                    face_turn_code(slice.layer().turn).map(|turn| (SLICE, Some(turn), None))
+                }
+            })
+            .collect();
+
+        let mut count = codes.len();
+        loop {
+            let byte = u8::try_from(count & 0x7f).unwrap_or_default();
+            count >>= 7;
+            writer.write(byte | u8::from(count > 0) << 7, 8);
+            if count == 0 {
+                break;
+            }
+        }
+        for (code, turn, layer) in codes {
+            writer.write(code, 5);
+            if let Some(turn) = turn {
+                writer.write(turn, 5);
+            }
+            if let Some(layer) = layer {
+                writer.write(layer.wide.into(), 1);
+                writer.write(layer.depth, 8);
+            }
+        }
+        [vec![VERSION], writer.bytes].concat()
+    }
+
+    /// Reads moves written by [`Algorithm::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// This errors when the bytes were not written by [`Algorithm::to_bytes`], or by a version
+    /// of it that this crate doesn't know
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = BitReader {
+            bytes: version(bytes)?,
+            used: 0,
+        };
+        let mut count = 0;
+        for shift in (0..).step_by(7) {
+            let byte = reader.read(8)?;
+            let part = usize::from(byte & 0x7f);
+            count |= part.checked_shl(shift).ok_or(DecodeError::TooLong)?;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let mut moves = Vec::new();
+        for _ in 0..count {
+            let code = reader.read(5)?;
+            let m = match code {
+                0..=17 => face_turn(code).map(Move::FaceTurn),
+                18..=26 => rotation(code).map(Move::Rotation),
+                LAYER => {
+                    let turn = reader.read(5)?;
+                    let wide = reader.read(1)? == 1;
+                    let depth = reader.read(8)?;
+                    face_turn(turn).map(|turn| Move::Layer(LayerTurn { turn, depth, wide }))
+                }
+                SLICE => match face_turn(reader.read(5)?) {
+                    Some(FaceTurn::L(t)) => Some(Move::Slice(SliceTurn::M(t))),
+                    Some(FaceTurn::D(t)) => Some(Move::Slice(SliceTurn::E(t))),
+                    Some(FaceTurn::F(t)) => Some(Move::Slice(SliceTurn::S(t))),
+                    _ => None,
+                },
+                _ => None,
+            };
+            moves.push(m.ok_or(DecodeError::UnknownMove(code))?);
+        }
+        if reader.used.div_ceil(8) < reader.bytes.len() {
+            return Err(DecodeError::TooLong);
+        }
+        Ok(Self { moves })
+    }
+}
+
+impl Cube {
+    /// Packs the state into [`CUBE_BYTES`] bytes, as described in
+    /// [the module docs](crate::encoding)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+    /// let bytes = cube.to_bytes();
+    ///
+    /// assert_eq!(bytes.len(), 11);
+    /// assert_eq!(Cube::from_bytes(&bytes), Ok(cube));
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; CUBE_BYTES] {
+        let coordinates = Coordinates::of(self);
+        let value = [
+            (
+                coordinates.corner_permutation,
+                Coordinates::CORNER_PERMUTATIONS,
+            ),
+            (coordinates.corner_twist, Coordinates::CORNER_TWISTS),
+            (coordinates.edge_permutation, Coordinates::EDGE_PERMUTATIONS),
+            (coordinates.edge_flip, Coordinates::EDGE_FLIPS),
+        ]
+        .into_iter()
+        .fold(coordinates.centers as u128, |value, (digit, base)| {
+            value * base as u128 + digit as u128
+        });
+        let mut bytes = [0; CUBE_BYTES];
+        bytes[0] = VERSION;
+        bytes[1..].copy_from_slice(&value.to_be_bytes()[16 - (CUBE_BYTES - 1)..]);
+        bytes
+    }
+
+    /// Reads a state written by [`Cube::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// This errors when the bytes were not written by [`Cube::to_bytes`], or by a version of it
+    /// that this crate doesn't know
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let rest = version(bytes)?;
+        match rest.len().cmp(&(CUBE_BYTES - 1)) {
+            std::cmp::Ordering::Less => return Err(DecodeError::TooShort),
+            std::cmp::Ordering::Greater => return Err(DecodeError::TooLong),
+            std::cmp::Ordering::Equal => {}
+        }
+        let mut value = rest
+            .iter()
+            .fold(0u128, |value, &byte| value << 8 | u128::from(byte));
+        let mut digit = |base: usize| {
+            let digit = usize::try_from(value % base as u128).map_err(|_| DecodeError::NotACube);
+            value /= base as u128;
+            digit
+        };
+        let edge_flip = digit(Coordinates::EDGE_FLIPS)?;
+        let edge_permutation = digit(Coordinates::EDGE_PERMUTATIONS)?;
+        let corner_twist = digit(Coordinates::CORNER_TWISTS)?;
+        let corner_permutation = digit(Coordinates::CORNER_PERMUTATIONS)?;
+        let centers = usize::try_from(value).map_err(|_| DecodeError::NotACube)?;
+        let coordinates = Coordinates {
+            centers,
+            corner_permutation,
+            corner_twist,
+            edge_permutation,
+            edge_flip,
+        };
+        if coordinates.is_valid() {
+            Ok(coordinates.cube())
+        } else {
+            Err(DecodeError::NotACube)
+        }
+    }
+}
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use crate::color_scheme::ColorScheme;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn every_kind_of_move_round_trips() {
+        let alg = Algorithm::from("U D2 F' B L2 R x y2 z' Rw 3Uw' 2F2 7Lw M' E2 S").unwrap();
+        let bytes = alg.to_bytes();
+
+        assert_eq!(Algorithm::from_bytes(&bytes), Ok(alg));
+    }
+
+    #[test]
+    fn face_turns_take_five_bits() {
+        let alg = Algorithm::from("R U R' U' R U R' U'").unwrap();
+
+        assert_eq!(alg.to_bytes().len(), 1 + 1 + 5);
+    }
+
+    #[test]
+    fn long_algorithms_round_trip() {
+        let alg: Algorithm = vec![FaceTurn::R(1); 300].into();
+        let bytes = alg.to_bytes();
+
+        assert_eq!(bytes[1..3], [0xac, 0x02]);
+        assert_eq!(Algorithm::from_bytes(&bytes), Ok(alg));
+    }
+
+    #[test]
+    fn empty_algorithm() {
+        assert_eq!(Algorithm::new().to_bytes(), vec![VERSION, 0]);
+        assert_eq!(Algorithm::from_bytes(&[VERSION, 0]), Ok(Algorithm::new()));
+    }
+
+    #[test]
+    fn bad_algorithm_bytes() {
+        assert_eq!(
+            Algorithm::from_bytes(&[VERSION + 1, 0]),
+            Err(DecodeError::UnknownVersion(VERSION + 1))
+        );
+        assert_eq!(
+            Algorithm::from_bytes(&[0, 0]),
+            Err(DecodeError::UnknownVersion(0))
+        );
+        assert_eq!(Algorithm::from_bytes(&[]), Err(DecodeError::TooShort));
+        assert_eq!(
+            Algorithm::from_bytes(&[VERSION, 2, 0]),
+            Err(DecodeError::TooShort)
+        );
+        assert_eq!(
+            Algorithm::from_bytes(&[VERSION, 1, 0b1111_1000]),
+            Err(DecodeError::UnknownMove(31))
+        );
+        assert_eq!(
+            Algorithm::from_bytes(&[VERSION, 0, 0]),
+            Err(DecodeError::TooLong)
+        );
+    }
+
+    #[test]
+    fn version_one_is_still_read() {
+        let alg = Algorithm::from("R U R' U'").unwrap();
+        let mut bytes = alg.to_bytes();
+        bytes[0] = 1;
+
+        assert_eq!(Algorithm::from_bytes(&bytes), Ok(alg));
+
+        let cube = Cube::new().apply(Algorithm::from("F2 D").unwrap());
+        let mut bytes = cube.to_bytes();
+        bytes[0] = 1;
+
+        assert_eq!(Cube::from_bytes(&bytes), Ok(cube));
+    }
+
+    #[test]
+    fn cubes_round_trip() {
+        let moves = Algorithm::from("R U2 F' x 2R Dw' L B2 z' 3F D' y").unwrap();
+        for cube in [
+            Cube::new(),
+            Cube::new().apply(moves.clone()),
+            Cube::new_with_scheme(ColorScheme::wca()).apply(moves),
+        ] {
+            assert_eq!(Cube::from_bytes(&cube.to_bytes()), Ok(cube));
+        }
+    }
+
+    #[test]
+    fn bad_cube_bytes() {
+        assert_eq!(Cube::from_bytes(&[VERSION; 10]), Err(DecodeError::TooShort));
+        assert_eq!(Cube::from_bytes(&[VERSION; 12]), Err(DecodeError::TooLong));
+        assert_eq!(
+            Cube::from_bytes(&[VERSION, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(DecodeError::NotACube)
+        );
+    }
+}
@@ -0,0 +1,609 @@
+//! Turns blindfolded memo, or a scramble, into the moves of a beginner blindfolded solve
+//!
+//! Both methods solve one piece at a time by trading it with the buffer. The target is brought
+//! to the helper spot by setup moves that leave the buffer alone, the swap algorithm trades the
+//! helper with the buffer, and the setup is undone. Swap algorithms move a few other pieces too,
+//! which are put back by every second swap. A target on one of those pieces can't be set up, so
+//! the swap is done as it is and a commutator fixes the rest, like the special cases of M2
+use std::sync::OnceLock;
+
+use crate::{
+    blind::{
+        corner_spot, edge_spot, spot_sticker, BlindError, LetterScheme, Memo, MemoOptions, Spot,
+    },
+    commutators,
+    cube::{Cube, Face},
+    group::Permutation,
+    moves::{Algorithm, FaceTurn},
+};
+
+const T_PERM: &str = "R U R' U' R' F R2 U' R' U' R U R' F'";
+const Y_PERM: &str = "R U' R' U' R U R' F' R U R' U' R' F R";
+const J_PERM: &str = "R U R' F' R U R' U' R' F R2 U' R'";
+
+/// More commutators than fixing the side effect of one swap needs
+const MAX_COMMUTATORS: usize = 6;
+
+/// A beginner method for solving blindfolded
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Method {
+    /// Edges are traded between UR and UL with a T-perm, and corners between UBL and RDF with a
+    /// Y-perm
+    OldPochmann,
+    /// Edges are traded between DF and UB with M2, and corners between DFR and UBR with R2
+    M2R2,
+}
+
+/// How one kind of piece is solved
+struct Phase {
+    corners: bool,
+    buffer: Spot,
+    swap: Algorithm,
+}
+
+impl Method {
+    /// Memo options with the buffers of this method, for memo that [`execute`] can follow
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{blind::LetterScheme, execution::Method};
+    ///
+    /// let options = Method::M2R2.memo_options(LetterScheme::speffz());
+    ///
+    /// assert_eq!(options.letters.edge(options.edge_buffer), Some('U'));
+    /// assert_eq!(options.letters.corner(options.corner_buffer), Some('V'));
+    /// ```
+    #[must_use]
+    pub fn memo_options(self, letters: LetterScheme) -> MemoOptions {
+        let [edges, corners] = self.phases();
+        MemoOptions {
+            letters,
+            edge_buffer: spot_sticker(false, edges.buffer),
+            corner_buffer: spot_sticker(true, corners.buffer),
+        }
+    }
+
+    fn phases(self) -> [Phase; 2] {
+        let (edge_buffer, edge_swap, corner_buffer, corner_swap) = match self {
+            Self::OldPochmann => ((0, 0), T_PERM, (6, 0), Y_PERM),
+            Self::M2R2 => ((9, 0), "2R2", (3, 0), "R2"),
+        };
+        let swap = |moves| Algorithm::from(moves).expect("the swap algorithms are valid");
+        [
+            Phase {
+                corners: false,
+                buffer: edge_buffer,
+                swap: swap(edge_swap),
+            },
+            Phase {
+                corners: true,
+                buffer: corner_buffer,
+                swap: swap(corner_swap),
+            },
+        ]
+    }
+}
+
+/// The moves for one target
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Step {
+    /// The letter of the sticker the buffer is traded with
+    pub target: char,
+    pub moves: Algorithm,
+}
+
+/// Every move of a blindfolded solve, target by target
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Execution {
+    pub edges: Vec<Step>,
+    pub corners: Vec<Step>,
+    /// The moves done at the end when there was an odd number of targets, which is empty when
+    /// none are needed
+    pub parity: Algorithm,
+}
+
+impl Execution {
+    /// Every move of the solve, in order
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.edges
+            .iter()
+            .chain(&self.corners)
+            .fold(Algorithm::new(), |all, step| all + &step.moves)
+            + &self.parity
+    }
+}
+
+const fn origin(corners: bool, permutation: &Permutation, spot: Spot) -> Spot {
+    if corners {
+        permutation.corner_origin(spot)
+    } else {
+        permutation.edge_origin(spot)
+    }
+}
+
+fn spots(corners: bool) -> Vec<Spot> {
+    let (positions, indices) = if corners { (8, 3) } else { (12, 2) };
+    (0..positions)
+        .flat_map(|position| (0..indices).map(move |index| (position, index)))
+        .collect()
+}
+
+/// Every sequence of up to `depth` face turns, shortest first, without turning a face twice in a row
+fn sequences(depth: usize) -> Vec<Algorithm> {
+    let turns: Vec<_> = Face::ALL
+        .into_iter()
+        .flat_map(|face| (1..=3).map(move |amount| face.turn(amount)))
+        .collect();
+    let mut all = vec![Vec::new()];
+    let mut start = 0;
+    for _ in 0..depth {
+        let end = all.len();
+        for i in start..end {
+            for &turn in &turns {
+                let last = all[i].last().map(|last: &FaceTurn| last.face().0);
+                if last != Some(turn.face().0) {
+                    let mut longer = all[i].clone();
+                    longer.push(turn);
+                    all.push(longer);
+                }
+            }
+        }
+        start = end;
+    }
+    all.into_iter().map(Into::into).collect()
+}
+
+/// Every sequence of up to three face turns with how it moves the pieces, which is worked out
+/// once
+fn short_sequences() -> &'static [(Algorithm, Permutation)] {
+    static SEQUENCES: OnceLock<Vec<(Algorithm, Permutation)>> = OnceLock::new();
+    SEQUENCES.get_or_init(|| {
+        sequences(3)
+            .into_iter()
+            .map(|moves| {
+                let permutation = Permutation::of(&moves);
+                (moves, permutation)
+            })
+            .collect()
+    })
+}
+
+/// The shortest setup that brings each sticker to `helper`, without moving the buffer or any of
+/// `fixed`, keyed by where the sticker starts
+fn setups(
+    corners: bool,
+    helper: Spot,
+    fixed: &Permutation,
+    buffer: usize,
+) -> Vec<(Spot, Algorithm)> {
+    let mut found: Vec<(Spot, Algorithm)> = Vec::new();
+    for (setup, permutation) in short_sequences() {
+        let keeps = |corners: bool, position: usize, moved: bool| {
+            !moved
+                || origin(corners, permutation, (position, 0)) == (position, 0)
+                    && origin(corners, permutation, (position, 1)) == (position, 1)
+        };
+        let keeps_all = (0..8).all(|p| {
+            keeps(
+                true,
+                p,
+                origin(true, fixed, (p, 0)) != (p, 0) || (corners && p == buffer),
+            )
+        }) && (0..12).all(|p| {
+            keeps(
+                false,
+                p,
+                origin(false, fixed, (p, 0)) != (p, 0) || (!corners && p == buffer),
+            )
+        });
+        let start = origin(corners, permutation, helper);
+        if keeps_all && !found.iter().any(|(spot, _)| *spot == start) {
+            found.push((start, setup.clone()));
+        }
+    }
+    found
+}
+
+/// Commutators that turn `from` into `to`, when they only differ by pieces of one kind
+///
+/// Each commutator puts one more sticker where it belongs. A piece turned in place is first
+/// cycled away, onto another piece that isn't solved when there is one
+fn fix(corners: bool, from: &Cube, to: &Cube) -> Option<Algorithm> {
+    let spots = spots(corners);
+    let mut cube = from.clone();
+    let mut moves = Algorithm::new();
+    for _ in 0..MAX_COMMUTATORS {
+        let permutation = Permutation::between(&cube, to)?;
+        let unsolved: Vec<Spot> = spots
+            .iter()
+            .copied()
+            .filter(|&spot| origin(corners, &permutation, spot) != spot)
+            .collect();
+        let Some(&wrong) = unsolved.first() else {
+            return Some(moves);
+        };
+        // spots on other pieces, for stickers to be cycled through
+        let others = |pieces: Vec<usize>| {
+            unsolved
+                .clone()
+                .into_iter()
+                .chain(spots.clone())
+                .filter(move |(position, _)| !pieces.contains(position))
+        };
+        let belongs = origin(corners, &permutation, wrong);
+        let cycles: Vec<[Spot; 3]> = if belongs.0 == wrong.0 {
+            others(vec![wrong.0])
+                .flat_map(|first| {
+                    others(vec![wrong.0, first.0]).map(move |second| [wrong, first, second])
+                })
+                .collect()
+        } else {
+            let next = origin(corners, &permutation, belongs);
+            std::iter::once(next)
+                .chain(others(vec![wrong.0, belongs.0]))
+                .filter(|third| third.0 != wrong.0 && third.0 != belongs.0)
+                .map(|third| [belongs, wrong, third])
+                .collect()
+        };
+        let commutator = cycles.into_iter().find_map(|cycle| {
+            let [a, b, c] = cycle.map(|spot| spot_sticker(corners, spot));
+            commutators::find(a, b, c, 1)
+                .ok()?
+                .first()
+                .map(commutators::Commutator::algorithm)
+        })?;
+        cube = cube.apply(commutator.clone());
+        moves = moves + &commutator;
+    }
+    None
+}
+
+/// Trades the buffer of `phase` with each of `targets` in turn, returning the steps
+fn solve_phase(
+    cube: &mut Cube,
+    phase: &Phase,
+    targets: &[Spot],
+    letters: &LetterScheme,
+) -> Result<(Vec<Step>, bool), BlindError> {
+    let corners = phase.corners;
+    let letter = |spot: Spot| {
+        if corners {
+            letters.corner_spot_letter(spot)
+        } else {
+            letters.edge_spot_letter(spot)
+        }
+    };
+    let swap = Permutation::of(&phase.swap);
+    let spots = spots(corners);
+    let helper = spots
+        .iter()
+        .copied()
+        .find(|&spot| origin(corners, &swap, spot) == phase.buffer)
+        .ok_or(BlindError::NoExecution)?;
+    // everything else the swap moves, which is put back by the next swap
+    let side_effect = if corners {
+        swap.without(&[phase.buffer.0, helper.0], &[])
+    } else {
+        swap.without(&[], &[phase.buffer.0, helper.0])
+    };
+    let setups = setups(corners, helper, &side_effect, phase.buffer.0);
+
+    let mut steps = Vec::new();
+    let mut odd = false;
+    for &target in targets {
+        let ideal = if odd {
+            side_effect.apply(cube)
+        } else {
+            cube.clone()
+        };
+        let traded = Permutation::swap(corners, phase.buffer, target).apply(&ideal);
+        let wanted = if odd {
+            traded
+        } else {
+            side_effect.apply(&traded)
+        };
+        let physical = if odd {
+            spots
+                .iter()
+                .copied()
+                .find(|&spot| origin(corners, &side_effect, spot) == target)
+                .unwrap_or(target)
+        } else {
+            target
+        };
+        let moves = if let Some((_, setup)) = setups.iter().find(|(start, _)| *start == physical) {
+            setup.permute(&phase.swap)
+        } else {
+            let swapped = cube.apply(phase.swap.clone());
+            let fix = fix(corners, &swapped, &wanted).ok_or(BlindError::NoExecution)?;
+            phase.swap.clone() + &fix
+        };
+        *cube = cube.apply(moves.clone());
+        if *cube != wanted {
+            return Err(BlindError::NoExecution);
+        }
+        odd = !odd;
+        steps.push(Step {
+            target: letter(target),
+            moves,
+        });
+    }
+    Ok((steps, odd))
+}
+
+/// Solves what is left after both kinds of piece have had an odd number of swaps, which is two
+/// edges and two corners that need trading
+///
+/// One of the swap algorithms set up by a few turns is used when one fits, and otherwise a T-perm
+/// leaves an even number of pieces out of place, which commutators solve
+fn parity(cube: &Cube) -> Option<Algorithm> {
+    let algs = [T_PERM, J_PERM, Y_PERM].map(|moves| Algorithm::from(moves).ok());
+    let set_up = short_sequences().iter().find_map(|(setup, _)| {
+        algs.iter().flatten().find_map(|alg| {
+            let moves = setup.permute(alg);
+            (cube.apply(moves.clone()) == Cube::new()).then_some(moves)
+        })
+    });
+    set_up.or_else(|| {
+        let swap = algs[0].clone()?;
+        let swapped = cube.apply(swap.clone());
+        let corners = fix(true, &swapped, &Cube::new())?;
+        let edges = fix(false, &swapped.apply(corners.clone()), &Cube::new())?;
+        let moves = swap + &corners + &edges;
+        // the centers aren't looked at by the commutators, so they may still be out of place
+        (cube.apply(moves.clone()) == Cube::new()).then_some(moves)
+    })
+}
+
+/// The spots of a memo's targets, with each piece that is turned in place traded as the sticker
+/// its U or D color is on, and then its U or D sticker
+fn targets(
+    phase: &Phase,
+    letters: &[char],
+    turned: &[char],
+    scheme: &LetterScheme,
+) -> Result<Vec<Spot>, BlindError> {
+    let corners = phase.corners;
+    let spot = |letter: char| {
+        let sticker = if corners {
+            scheme.corner_sticker(letter)
+        } else {
+            scheme.edge_sticker(letter)
+        }
+        .ok_or(BlindError::UnknownLetter(letter))?;
+        let spot = if corners {
+            corner_spot(sticker)?
+        } else {
+            edge_spot(sticker)?
+        };
+        if spot.0 == phase.buffer.0 {
+            return Err(BlindError::SamePiece(
+                spot_sticker(corners, phase.buffer),
+                sticker,
+            ));
+        }
+        Ok(spot)
+    };
+    let mut spots = letters
+        .iter()
+        .map(|&letter| spot(letter))
+        .collect::<Result<Vec<_>, _>>()?;
+    for &letter in turned {
+        let turned = spot(letter)?;
+        spots.extend([turned, (turned.0, 0)]);
+    }
+    Ok(spots)
+}
+
+/// Works out every move to solve the cube that `memo` was made of, edges first, then corners
+///
+/// The memo must be made with the buffers of `method`, which [`Method::memo_options`] gives, and
+/// each step trades the buffer with the next target. Pieces that are in place but flipped or
+/// twisted are traded as two targets, after the other targets of their kind
+///
+/// # Errors
+///
+/// This errors when a letter isn't in `letters`, when a target is on the buffer, or when the moves
+/// found don't solve the cube the memo describes, which happens when the memo can't come from a
+/// cube
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     blind::{LetterScheme, Memo},
+///     execution::{self, Method},
+///     moves::Algorithm,
+/// };
+///
+/// let scramble = Algorithm::from("R U R' U'").unwrap();
+/// let options = Method::OldPochmann.memo_options(LetterScheme::speffz());
+/// let memo = Memo::scramble(&scramble, &options).unwrap();
+/// let execution = execution::execute(&memo, Method::OldPochmann, &options.letters).unwrap();
+///
+/// assert!(execution.algorithm().solves(&scramble));
+/// ```
+pub fn execute(
+    memo: &Memo,
+    method: Method,
+    letters: &LetterScheme,
+) -> Result<Execution, BlindError> {
+    let [edge_phase, corner_phase] = method.phases();
+    let edge_targets = targets(&edge_phase, &memo.edges, &memo.flipped_edges, letters)?;
+    let corner_targets = targets(&corner_phase, &memo.corners, &memo.twisted_corners, letters)?;
+    // each trade undoes itself, so doing them last to first on a solved cube gives the memo's cube
+    let start = [
+        (&edge_phase, &edge_targets),
+        (&corner_phase, &corner_targets),
+    ]
+    .into_iter()
+    .fold(Cube::new(), |cube, (phase, targets)| {
+        targets.iter().rev().fold(cube, |cube, &target| {
+            Permutation::swap(phase.corners, phase.buffer, target).apply(&cube)
+        })
+    });
+
+    let mut cube = start.clone();
+    let (edges, edges_odd) = solve_phase(&mut cube, &edge_phase, &edge_targets, letters)?;
+    let (corners, corners_odd) = solve_phase(&mut cube, &corner_phase, &corner_targets, letters)?;
+
+    let mut execution = Execution {
+        edges,
+        corners,
+        parity: Algorithm::new(),
+    };
+    if cube != Cube::new() {
+        // a swap algorithm set up by a few turns usually trades what is left, and otherwise the
+        // swaps of the odd phases are done again, so that commutators can solve the rest
+        execution.parity = parity(&cube)
+            .or_else(|| {
+                let swaps = [(edges_odd, &edge_phase), (corners_odd, &corner_phase)]
+                    .into_iter()
+                    .filter(|(odd, _)| *odd)
+                    .fold(Algorithm::new(), |all, (_, phase)| all + &phase.swap);
+                let rest = parity(&cube.apply(swaps.clone()))?;
+                Some(swaps + &rest)
+            })
+            .ok_or(BlindError::NoExecution)?;
+    }
+    if start.apply(execution.algorithm()) != Cube::new() {
+        return Err(BlindError::NoExecution);
+    }
+    Ok(execution)
+}
+
+/// Memorizes a scramble with the buffers of `method`, then works out every move to solve it
+/// blindfolded with [`execute`]
+///
+/// The scramble should only turn faces, so that the centers stay where they are
+///
+/// # Errors
+///
+/// This errors when the moves for a target can't be found, or when the moves found don't solve
+/// the scramble, neither of which happens for scrambles that only turn faces
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     blind::LetterScheme,
+///     execution::{self, Method},
+///     moves::Algorithm,
+/// };
+///
+/// let scramble = Algorithm::from("R U R' U'").unwrap();
+/// let execution = execution::solve(&scramble, Method::OldPochmann, &LetterScheme::speffz()).unwrap();
+///
+/// assert!(execution.algorithm().solves(&scramble));
+/// ```
+pub fn solve(
+    scramble: &Algorithm,
+    method: Method,
+    letters: &LetterScheme,
+) -> Result<Execution, BlindError> {
+    let memo = Memo::scramble(scramble, &method.memo_options(letters.clone()))?;
+    let execution = execute(&memo, method, letters)?;
+    if !execution.algorithm().solves(scramble) {
+        return Err(BlindError::NoExecution);
+    }
+    Ok(execution)
+}
+
+#[cfg(test)]
+mod execution_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    const SCRAMBLES: [&str; 3] = [
+        "R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F",
+        "D2 F' D2 U2 F' L2 D R2 D B2 F L2 R' F' D U'",
+        "U",
+    ];
+
+    #[test]
+    fn old_pochmann_solves_scrambles() {
+        for scramble in SCRAMBLES {
+            let scramble = Algorithm::from(scramble).unwrap();
+            let execution = solve(&scramble, Method::OldPochmann, &LetterScheme::speffz()).unwrap();
+
+            assert!(execution.algorithm().solves(&scramble), "{scramble}");
+            assert_eq!(
+                execution.parity.moves.is_empty(),
+                !memo(&scramble, Method::OldPochmann).parity,
+                "{scramble}"
+            );
+        }
+    }
+
+    #[test]
+    fn m2_r2_solves_scrambles() {
+        for scramble in SCRAMBLES {
+            let scramble = Algorithm::from(scramble).unwrap();
+            let execution = solve(&scramble, Method::M2R2, &LetterScheme::speffz()).unwrap();
+
+            assert!(execution.algorithm().solves(&scramble), "{scramble}");
+        }
+    }
+
+    fn memo(scramble: &Algorithm, method: Method) -> Memo {
+        Memo::scramble(scramble, &method.memo_options(LetterScheme::speffz())).unwrap()
+    }
+
+    #[test]
+    fn steps_follow_the_memo() {
+        let scramble = Algorithm::from("U").unwrap();
+        let memo = memo(&scramble, Method::OldPochmann);
+        let execution = execute(&memo, Method::OldPochmann, &LetterScheme::speffz()).unwrap();
+        let targets = |steps: &[Step]| steps.iter().map(|step| step.target).collect::<Vec<_>>();
+
+        assert_eq!(targets(&execution.edges), memo.edges);
+        assert_eq!(targets(&execution.corners), memo.corners);
+        assert_eq!(memo.edges, vec!['A', 'D', 'C']);
+        assert_eq!(
+            execution.edges[1].moves,
+            Algorithm::from(T_PERM).unwrap(),
+            "the target on the helper needs no setup"
+        );
+        assert!(execution.algorithm().solves(&scramble));
+    }
+
+    #[test]
+    fn turned_pieces_are_traded_twice() {
+        let scramble = Algorithm::from("R U R' U R U2 R' M' U M' U M' U M' U").unwrap();
+        for method in [Method::OldPochmann, Method::M2R2] {
+            let memo = memo(&scramble, method);
+            let execution = execute(&memo, method, &LetterScheme::speffz()).unwrap();
+
+            assert!(!memo.flipped_edges.is_empty() || !memo.twisted_corners.is_empty());
+            assert_eq!(
+                execution.edges.len(),
+                memo.edges.len() + 2 * memo.flipped_edges.len()
+            );
+            assert!(execution.algorithm().solves(&scramble), "{method:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_memo_that_cant_be_followed() {
+        let speffz = LetterScheme::speffz();
+        let memo = |edges: &str| Memo {
+            edges: edges.chars().collect(),
+            ..Memo::default()
+        };
+
+        assert_eq!(
+            execute(&memo("A?"), Method::OldPochmann, &speffz),
+            Err(BlindError::UnknownLetter('?'))
+        );
+        assert_eq!(
+            execute(&memo("AM"), Method::OldPochmann, &speffz),
+            Err(BlindError::SamePiece(
+                speffz.edge_sticker('B').unwrap(),
+                speffz.edge_sticker('M').unwrap()
+            ))
+        );
+    }
+}
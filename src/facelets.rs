@@ -0,0 +1,263 @@
+//! Reads and writes a [`Cube`] as a string of sticker colors
+//!
+//! The stickers are listed face by face in the order U, R, F, D, L, B, the same order that
+//! Kociemba's solver uses, and each face is read row by row as it is laid out in the printed net.
+//! Each sticker is the first letter of its color, one of `Y W R O B G`
+use thiserror::Error;
+
+use crate::{
+    color_scheme::{ColorScheme, ColorSchemeError},
+    cube::{Cube, Face, Side},
+    stickers::{Place, Sticker, Stickers},
+    terminal,
+};
+
+const FACE_ORDER: [Face; 6] = [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B];
+
+/// Occurs when a string of sticker colors can't be a cube
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FaceletError {
+    #[error("a cube has 54 stickers, but {0} were given")]
+    WrongLength(usize),
+    #[error("{0} is not the first letter of a color")]
+    UnknownColor(char),
+    #[error("more than one center is {0:?}")]
+    RepeatedCenter(Side),
+    #[error("{0} is not a piece of this cube")]
+    UnknownPiece(String),
+    #[error("more than one piece is {0}")]
+    RepeatedPiece(String),
+    #[error("the corners are twisted in a way that can't be solved")]
+    TwistedCorners,
+    #[error("the edges are flipped in a way that can't be solved")]
+    FlippedEdges,
+    #[error("two pieces are swapped in a way that can't be solved")]
+    SwappedPieces,
+}
+
+fn letters(colors: &[Side]) -> String {
+    colors.iter().map(|&side| terminal::letter(side)).collect()
+}
+
+const fn side(letter: char) -> Result<Side, FaceletError> {
+    match letter {
+        'Y' => Ok(Side::Yellow),
+        'W' => Ok(Side::White),
+        'R' => Ok(Side::Red),
+        'O' => Ok(Side::Orange),
+        'B' => Ok(Side::Blue),
+        'G' => Ok(Side::Green),
+        other => Err(FaceletError::UnknownColor(other)),
+    }
+}
+
+fn stickers() -> impl Iterator<Item = Sticker> {
+    FACE_ORDER.into_iter().flat_map(|face| {
+        (0..3).flat_map(move |row| (0..3).map(move |col| Sticker { face, row, col }))
+    })
+}
+
+/// Whether a list of distinct positions is an odd permutation
+fn is_odd(homes: &[usize]) -> bool {
+    let swaps: usize = (0..homes.len())
+        .map(|i| {
+            (i + 1..homes.len())
+                .filter(|&j| homes[j] < homes[i])
+                .count()
+        })
+        .sum();
+    swaps % 2 == 1
+}
+
+impl Cube {
+    /// Lists the color of every sticker, as described in [the module docs](crate::facelets)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("U").unwrap());
+    ///
+    /// assert_eq!(
+    ///     cube.facelets(),
+    ///     "YYYYYYYYYOOOGGGGGGGGGRRRRRRWWWWWWWWWRRRBBBBBBBBBOOOOOO"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn facelets(&self) -> String {
+        letters(
+            &stickers()
+                .map(|sticker| self.color(sticker))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Reads a cube from the color of every sticker, as described in
+    /// [the module docs](crate::facelets)
+    ///
+    /// Whitespace is ignored, so the faces can be split over several lines
+    ///
+    /// # Errors
+    ///
+    /// This errors when the stickers don't make up a cube that can be solved, like when a corner
+    /// is twisted in place, or two edges are swapped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, facelets::FaceletError, moves::Algorithm};
+    ///
+    /// let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+    ///
+    /// assert_eq!(Cube::from_facelets(&cube.facelets()), Ok(cube));
+    /// assert_eq!(
+    ///     Cube::from_facelets("YYYYYYYYY"),
+    ///     Err(FaceletError::WrongLength(9))
+    /// );
+    /// ```
+    pub fn from_facelets(facelets: &str) -> Result<Self, FaceletError> {
+        let colors = facelets
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(side)
+            .collect::<Result<Vec<_>, _>>()?;
+        if colors.len() != 54 {
+            return Err(FaceletError::WrongLength(colors.len()));
+        }
+
+        let mut cube = Self::new();
+        for (sticker, color) in stickers().zip(colors) {
+            match sticker.place(3) {
+                Place::Corner(position, index) => cube.corners[position].colors[index] = color,
+                Place::Edge(position, index) => cube.edges[position].colors[index] = color,
+                Place::Center(face) => cube.centers[face as usize].color = color,
+            }
+        }
+
+        let [up, down, front, back, left, right] = Face::ALL.map(|face| cube.center(face));
+        let scheme =
+            ColorScheme::new(up, down, front, back, left, right).map_err(|error| match error {
+                ColorSchemeError::RepeatedColor(side) => FaceletError::RepeatedCenter(side),
+                ColorSchemeError::NotAdjacent(..) => unreachable!("no colors were held"),
+            })?;
+        let solved = Self::new_with_scheme(scheme);
+
+        let mut corner_homes = Vec::new();
+        let mut twists = 0;
+        for corner in cube.corners {
+            let found = (0..8).find_map(|home| {
+                let goal = solved.corners[home].colors;
+                (0..3)
+                    .find(|&twist| (0..3).all(|i| corner.colors[(twist + i) % 3] == goal[i]))
+                    .map(|twist| (home, twist))
+            });
+            let name = letters(&corner.colors);
+            let (home, twist) = found.ok_or_else(|| FaceletError::UnknownPiece(name.clone()))?;
+            if corner_homes.contains(&home) {
+                return Err(FaceletError::RepeatedPiece(name));
+            }
+            corner_homes.push(home);
+            twists += twist;
+        }
+
+        let mut edge_homes = Vec::new();
+        let mut flips = 0;
+        for edge in cube.edges {
+            let [a, b] = edge.colors;
+            let found = (0..12).find_map(|home| match solved.edges[home].colors {
+                goal if goal == [a, b] => Some((home, 0)),
+                goal if goal == [b, a] => Some((home, 1)),
+                _ => None,
+            });
+            let name = letters(&edge.colors);
+            let (home, flip) = found.ok_or_else(|| FaceletError::UnknownPiece(name.clone()))?;
+            if edge_homes.contains(&home) {
+                return Err(FaceletError::RepeatedPiece(name));
+            }
+            edge_homes.push(home);
+            flips += flip;
+        }
+
+        if twists % 3 != 0 {
+            Err(FaceletError::TwistedCorners)
+        } else if flips % 2 != 0 {
+            Err(FaceletError::FlippedEdges)
+        } else if is_odd(&corner_homes) != is_odd(&edge_homes) {
+            Err(FaceletError::SwappedPieces)
+        } else {
+            Ok(cube)
+        }
+    }
+}
+
+#[cfg(test)]
+mod facelets_tests {
+    use super::*;
+    use crate::moves::Algorithm;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn round_trips() {
+        let cube = Cube::new_with_scheme(ColorScheme::wca())
+            .apply(Algorithm::from("R U2 F' x 2R Dw' L B2 z'").unwrap());
+
+        assert_eq!(Cube::from_facelets(&cube.facelets()), Ok(cube));
+    }
+
+    #[test]
+    fn solved_facelets() {
+        assert_str_eq!(
+            Cube::new().facelets(),
+            "YYYYYYYYYGGGGGGGGGRRRRRRRRRWWWWWWWWWBBBBBBBBBOOOOOOOOO"
+        );
+    }
+
+    #[test]
+    fn ignores_whitespace() {
+        let facelets =
+            "YYY YYY YYY\nGGG GGG GGG\nRRR RRR RRR\nWWW WWW WWW\nBBB BBB BBB\nOOO OOO OOO";
+
+        assert_eq!(Cube::from_facelets(facelets), Ok(Cube::new()));
+    }
+
+    fn with(changes: &[(usize, char)]) -> String {
+        let mut facelets: Vec<char> = Cube::new().facelets().chars().collect();
+        for &(i, c) in changes {
+            facelets[i] = c;
+        }
+        facelets.into_iter().collect()
+    }
+
+    #[test]
+    fn rejects_impossible_cubes() {
+        assert_eq!(
+            Cube::from_facelets(&with(&[(0, 'X')])),
+            Err(FaceletError::UnknownColor('X'))
+        );
+        assert_eq!(
+            Cube::from_facelets(&with(&[(4, 'W')])),
+            Err(FaceletError::RepeatedCenter(Side::White))
+        );
+        // the UF edge flipped in place
+        assert_eq!(
+            Cube::from_facelets(&with(&[(7, 'R'), (19, 'Y')])),
+            Err(FaceletError::FlippedEdges)
+        );
+        // the UFR corner twisted in place
+        assert_eq!(
+            Cube::from_facelets(&with(&[(8, 'G'), (9, 'R'), (20, 'Y')])),
+            Err(FaceletError::TwistedCorners)
+        );
+        // the UF and UR edges swapped
+        assert_eq!(
+            Cube::from_facelets(&with(&[(7, 'Y'), (19, 'G'), (5, 'Y'), (10, 'R')])),
+            Err(FaceletError::SwappedPieces)
+        );
+        assert_eq!(
+            Cube::from_facelets(&with(&[(19, 'W')])),
+            Err(FaceletError::UnknownPiece("YW".to_string()))
+        );
+    }
+}
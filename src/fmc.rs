@@ -0,0 +1,327 @@
+//! Tools for fewest moves solving, where moves can be found on the scramble or on its inverse
+//!
+//! Moves found on the inverse scramble are written in parentheses, like `R U (F2 D')`. Doing
+//! `F2 D'` after the inverse scramble is the same as doing `D F2` at the end of the solution, so
+//! the whole solution here is `R U D F2`. Switching between the two is called NISS, for normal
+//! inverse scramble switch
+//!
+//! A skeleton that leaves only a few pieces unsolved can be finished by inserting a commutator
+//! somewhere in it, which [`insertions`] searches for
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    cube::Cube,
+    group::Permutation,
+    moves::{Algorithm, Metric, MoveParseError},
+};
+
+/// Occurs when a string cannot be read as a [`Niss`] solution
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NissError {
+    #[error("a parenthesis was opened inside another one")]
+    NestedParenthesis,
+    #[error("a parenthesis was not closed, or closed without being opened")]
+    UnmatchedParenthesis,
+    #[error(transparent)]
+    Parse(#[from] MoveParseError),
+}
+
+/// Moves that were all found on the same side
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct Segment {
+    pub moves: Algorithm,
+    /// Whether the moves were found on the inverse scramble
+    pub inverse: bool,
+}
+
+/// A partial solution, made of moves found on the normal and the inverse scramble, in the order
+/// they were found
+#[derive(Debug, PartialEq, Eq, Clone, Default, PartialOrd, Ord, Hash)]
+pub struct Niss {
+    pub segments: Vec<Segment>,
+}
+
+impl Niss {
+    /// Creates a [`Niss`] solution without any moves
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Reads a solution in FMC notation, where moves in parentheses were found on the inverse
+    ///
+    /// # Errors
+    ///
+    /// This errors when the parentheses don't match up, or the moves can't be read as an
+    /// [`Algorithm`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{fmc::Niss, moves::Algorithm};
+    ///
+    /// let solution = Niss::from("R U (F2 D') L").unwrap();
+    ///
+    /// assert_eq!(solution.normal(), Algorithm::from("R U L").unwrap());
+    /// assert_eq!(solution.inverse(), Algorithm::from("F2 D'").unwrap());
+    /// ```
+    pub fn from(s: &str) -> Result<Self, NissError> {
+        let mut niss = Self::new();
+        let mut rest = s;
+        let mut inverse = false;
+        while let Some(end) = rest.find(['(', ')']) {
+            let opening = rest[end..].starts_with('(');
+            if opening == inverse {
+                return Err(if opening {
+                    NissError::NestedParenthesis
+                } else {
+                    NissError::UnmatchedParenthesis
+                });
+            }
+            niss.push(Algorithm::from(rest[..end].trim())?, inverse);
+            inverse = opening;
+            rest = &rest[end + 1..];
+        }
+        if inverse {
+            return Err(NissError::UnmatchedParenthesis);
+        }
+        niss.push(Algorithm::from(rest.trim())?, false);
+        Ok(niss)
+    }
+
+    /// Adds moves found on one side, joining them onto the last segment when it is the same side
+    pub fn push(&mut self, moves: Algorithm, inverse: bool) {
+        if moves == Algorithm::new() {
+            return;
+        }
+        match self.segments.last_mut() {
+            Some(last) if last.inverse == inverse => last.moves = last.moves.clone() + &moves,
+            _ => self.segments.push(Segment { moves, inverse }),
+        }
+    }
+
+    fn side(&self, inverse: bool) -> Algorithm {
+        self.segments
+            .iter()
+            .filter(|segment| segment.inverse == inverse)
+            .fold(Algorithm::new(), |all, segment| all + &segment.moves)
+    }
+
+    /// Every move found on the normal scramble, in order
+    #[must_use]
+    pub fn normal(&self) -> Algorithm {
+        self.side(false)
+    }
+
+    /// Every move found on the inverse scramble, in order
+    #[must_use]
+    pub fn inverse(&self) -> Algorithm {
+        self.side(true)
+    }
+
+    /// The cube after the scramble and the moves found on the normal side
+    #[must_use]
+    pub fn normal_cube(&self, scramble: &Algorithm) -> Cube {
+        Cube::new().apply(scramble.clone() + &self.normal())
+    }
+
+    /// The cube after the inverse scramble and the moves found on the inverse side
+    #[must_use]
+    pub fn inverse_cube(&self, scramble: &Algorithm) -> Cube {
+        Cube::new().apply(scramble.inverse() + &self.inverse())
+    }
+
+    /// The solution as it is done after the scramble, which is the normal moves followed by the
+    /// inverse of the inverse moves
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{fmc::Niss, moves::Algorithm};
+    ///
+    /// let solution = Niss::from("R U (F2 D') L").unwrap();
+    ///
+    /// assert_eq!(solution.algorithm(), Algorithm::from("R U L D F2").unwrap());
+    /// ```
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.normal() + &self.inverse().inverse()
+    }
+}
+
+impl Display for Niss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| {
+                if segment.inverse {
+                    format!("({})", segment.moves)
+                } else {
+                    segment.moves.to_string()
+                }
+            })
+            .join(" ");
+        write!(f, "{segments}")
+    }
+}
+
+/// A commutator put into a skeleton, and the solution that makes
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct Insertion {
+    /// How many moves of the skeleton are done before the commutator
+    pub position: usize,
+    pub moves: Algorithm,
+    /// The skeleton with the commutator inserted, with the moves that cancel combined
+    pub solution: Algorithm,
+}
+
+/// Tries every algorithm of `library` at every position of `skeleton`, and returns the best
+/// `limit` insertions that solve the scramble, shortest first
+///
+/// The scramble and skeleton should only turn faces or layers, so that the centers stay where
+/// they are. Nothing is found when the skeleton needs more than one insertion
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     commutators, fmc,
+///     moves::{Algorithm, Metric},
+/// };
+///
+/// let scramble = Algorithm::from("F U D R U R' D' R U' R' F'").unwrap();
+/// let skeleton = Algorithm::from("F U' F'").unwrap();
+/// let found = fmc::insertions(&scramble, &skeleton, &commutators::three_cycles(true), 1);
+///
+/// assert_eq!(found[0].solution.length(Metric::Htm), 11);
+/// assert!(found[0].solution.solves(&scramble));
+/// ```
+#[must_use]
+pub fn insertions(
+    scramble: &Algorithm,
+    skeleton: &Algorithm,
+    library: &[Algorithm],
+    limit: usize,
+) -> Vec<Insertion> {
+    let mut by_permutation: HashMap<Permutation, Vec<&Algorithm>> = HashMap::new();
+    for moves in library {
+        by_permutation
+            .entry(Permutation::of(moves))
+            .or_default()
+            .push(moves);
+    }
+
+    let mut found: Vec<Insertion> = Vec::new();
+    for position in 0..=skeleton.moves.len() {
+        let before: Algorithm = skeleton.moves[..position].to_vec().into();
+        let after: Algorithm = skeleton.moves[position..].to_vec().into();
+        let reached = Cube::new().apply(scramble.clone() + &before);
+        let needed = Cube::new().apply(after.inverse());
+        let Some(algs) = Permutation::between(&reached, &needed)
+            .and_then(|permutation| by_permutation.get(&permutation))
+        else {
+            continue;
+        };
+        for &moves in algs {
+            let solution = (before.clone() + moves + &after).simplify();
+            if !found.iter().any(|insertion| insertion.solution == solution) {
+                found.push(Insertion {
+                    position,
+                    moves: moves.clone(),
+                    solution,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|insertion| (insertion.solution.length(Metric::Htm), insertion.clone()));
+    found.truncate(limit);
+    found
+}
+
+#[cfg(test)]
+mod fmc_tests {
+    use super::*;
+    use crate::commutators::three_cycles;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    const SCRAMBLE: &str = "R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F";
+
+    #[test]
+    fn displays_inverse_moves_in_parentheses() {
+        let solution = Niss::from("R U  (F2 D')(B) L2 ").unwrap();
+
+        assert_str_eq!(solution.to_string(), "R U (F2 D' B) L2");
+        assert_eq!(Niss::from(&solution.to_string()), Ok(solution));
+    }
+
+    #[test]
+    fn parentheses_must_match() {
+        assert_eq!(Niss::from("R (U (F))"), Err(NissError::NestedParenthesis));
+        assert_eq!(Niss::from("R (U"), Err(NissError::UnmatchedParenthesis));
+        assert_eq!(Niss::from("R) U"), Err(NissError::UnmatchedParenthesis));
+        assert!(matches!(Niss::from("(R Q)"), Err(NissError::Parse(_))));
+    }
+
+    #[test]
+    fn both_sides_make_one_solution() {
+        let scramble = Algorithm::from(SCRAMBLE).unwrap();
+        let found = Algorithm::from("D2 F' D2 U2 F' L2 D R2 D B2 F L2 R' F' D U'").unwrap();
+        let mut solution = Niss::new();
+        solution.push(Algorithm::from("D2 F' D2 U2 F'").unwrap(), false);
+        solution.push(Algorithm::from("U D' F R").unwrap(), true);
+        solution.push(Algorithm::from("L2 D R2 D").unwrap(), false);
+        solution.push(Algorithm::from("L2 F' B2").unwrap(), true);
+
+        assert_eq!(solution.algorithm(), found);
+        assert!(solution.algorithm().solves(&scramble));
+    }
+
+    #[test]
+    fn each_side_has_its_own_cube() {
+        let scramble = Algorithm::from("R U F").unwrap();
+        let solution = Niss::from("F' (R) U'").unwrap();
+
+        assert_eq!(
+            solution.normal_cube(&scramble),
+            Cube::new().apply(Algorithm::from("R U F F' U'").unwrap())
+        );
+        assert_eq!(
+            solution.inverse_cube(&scramble),
+            Cube::new().apply(Algorithm::from("F' U' R' R").unwrap())
+        );
+    }
+
+    #[test]
+    fn insertions_solve_the_scramble() {
+        let skeleton = Algorithm::from("D2 F' D2 U2 F' L2 D R2 D B2 F L2 R' F' D U'").unwrap();
+        let commutator = Algorithm::from("R U R' D R U' R' D'").unwrap();
+        // a scramble that the skeleton leaves three corners unsolved on
+        let scramble = commutator.inverse() + &skeleton.inverse();
+        let found = insertions(&scramble, &skeleton, &three_cycles(true), 5);
+
+        assert!(!found.is_empty());
+        for insertion in &found {
+            assert!(insertion.solution.solves(&scramble), "{insertion:?}");
+        }
+        assert!(found.windows(2).all(|pair| {
+            pair[0].solution.length(Metric::Htm) <= pair[1].solution.length(Metric::Htm)
+        }));
+    }
+
+    #[test]
+    fn nothing_is_inserted_into_a_skeleton_needing_more() {
+        let scramble = Algorithm::from(SCRAMBLE).unwrap();
+
+        assert_eq!(
+            insertions(&scramble, &Algorithm::new(), &three_cycles(true), 5),
+            Vec::new()
+        );
+    }
+}
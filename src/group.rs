@@ -0,0 +1,356 @@
+//! Treats cube states as the moves that made them, so they can be combined like algorithms
+//!
+//! A state is read as whatever was done to its solved cube to get there, where the solved cube
+//! has the same colors held with yellow on top and the first color after it in front. For the
+//! usual colors that is [`Cube::new`], so `Cube::new().apply(alg)` is read as `alg`
+use crate::{
+    color_scheme::ColorScheme,
+    cube::{Cube, Side},
+    moves::Algorithm,
+};
+
+/// A piece's position, and which of its colors a sticker is, in the order of the position's faces
+pub(crate) type Spot = (usize, usize);
+
+/// Where every piece is taken from, and how it is turned on the way
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub(crate) struct Permutation {
+    /// The position each corner comes from, and how many times its colors are shifted
+    corners: [(usize, usize); 8],
+    /// The position each edge comes from, and whether it is flipped
+    edges: [(usize, bool); 12],
+    /// The face each center comes from
+    centers: [usize; 6],
+}
+
+impl Permutation {
+    /// The permutation that leaves every piece where it is
+    pub(crate) const IDENTITY: Self = Self {
+        corners: [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (4, 0),
+            (5, 0),
+            (6, 0),
+            (7, 0),
+        ],
+        edges: [
+            (0, false),
+            (1, false),
+            (2, false),
+            (3, false),
+            (4, false),
+            (5, false),
+            (6, false),
+            (7, false),
+            (8, false),
+            (9, false),
+            (10, false),
+            (11, false),
+        ],
+        centers: [0, 1, 2, 3, 4, 5],
+    };
+
+    /// Trades two corners, or two edges, so that the stickers on `a` and `b` trade places
+    pub(crate) const fn swap(corners: bool, a: Spot, b: Spot) -> Self {
+        let mut swap = Self::IDENTITY;
+        if corners {
+            swap.corners[a.0] = (b.0, (b.1 + 3 - a.1) % 3);
+            swap.corners[b.0] = (a.0, (a.1 + 3 - b.1) % 3);
+        } else {
+            swap.edges[a.0] = (b.0, a.1 != b.1);
+            swap.edges[b.0] = (a.0, a.1 != b.1);
+        }
+        swap
+    }
+
+    /// The same permutation, but leaving the given corners and edges where they are
+    pub(crate) fn without(&self, corners: &[usize], edges: &[usize]) -> Self {
+        let mut without = *self;
+        for &position in corners {
+            without.corners[position] = (position, 0);
+        }
+        for &position in edges {
+            without.edges[position] = (position, false);
+        }
+        without
+    }
+
+    /// Finds where every piece of `to` is in `from`, or `None` when they don't have the same pieces
+    pub(crate) fn between(from: &Cube, to: &Cube) -> Option<Self> {
+        let mut permutation = Self {
+            corners: [(0, 0); 8],
+            edges: [(0, false); 12],
+            centers: [0; 6],
+        };
+        for (position, corner) in to.corners.iter().enumerate() {
+            permutation.corners[position] = (0..8)
+                .flat_map(|home| (0..3).map(move |shift| (home, shift)))
+                .find(|&(home, shift)| {
+                    (0..3).all(|i| corner.colors[i] == from.corners[home].colors[(i + shift) % 3])
+                })?;
+        }
+        for (position, edge) in to.edges.iter().enumerate() {
+            let [a, b] = edge.colors;
+            permutation.edges[position] =
+                (0..12).find_map(|home| match from.edges[home].colors {
+                    colors if colors == [a, b] => Some((home, false)),
+                    colors if colors == [b, a] => Some((home, true)),
+                    _ => None,
+                })?;
+        }
+        for (position, center) in to.centers.iter().enumerate() {
+            permutation.centers[position] = from.centers.iter().position(|c| c == center)?;
+        }
+        Some(permutation)
+    }
+
+    /// How `moves` move the pieces of a solved cube
+    pub(crate) fn of(moves: &Algorithm) -> Self {
+        Cube::new().apply(moves.clone()).permutation()
+    }
+
+    /// The corner sticker that is moved onto `spot`
+    pub(crate) const fn corner_origin(&self, (position, index): Spot) -> Spot {
+        let (home, shift) = self.corners[position];
+        (home, (index + shift) % 3)
+    }
+
+    /// The edge sticker that is moved onto `spot`
+    pub(crate) const fn edge_origin(&self, (position, index): Spot) -> Spot {
+        let (home, flipped) = self.edges[position];
+        (home, index ^ flipped as usize)
+    }
+
+    /// How many corners and edges are moved or turned, when the centers stay where they are
+    pub(crate) fn moved(&self) -> Option<(usize, usize)> {
+        let centers_stay = (0..6).all(|face| self.centers[face] == face);
+        centers_stay.then(|| {
+            (
+                (0..8).filter(|&p| self.corners[p] != (p, 0)).count(),
+                (0..12).filter(|&p| self.edges[p] != (p, false)).count(),
+            )
+        })
+    }
+
+    /// Moves the pieces of `cube` the way this permutation says
+    pub(crate) fn apply(&self, cube: &Cube) -> Cube {
+        let mut moved = cube.clone();
+        for (corner, &(home, shift)) in moved.corners.iter_mut().zip(&self.corners) {
+            corner.colors = [0, 1, 2].map(|i| cube.corners[home].colors[(i + shift) % 3]);
+        }
+        for (edge, &(home, flipped)) in moved.edges.iter_mut().zip(&self.edges) {
+            let [a, b] = cube.edges[home].colors;
+            edge.colors = if flipped { [b, a] } else { [a, b] };
+        }
+        for (center, &home) in moved.centers.iter_mut().zip(&self.centers) {
+            *center = cube.centers[home];
+        }
+        moved
+    }
+
+    /// The permutation that puts every piece back where this one took it from
+    fn inverse(&self) -> Self {
+        let mut inverse = *self;
+        for (position, &(home, shift)) in self.corners.iter().enumerate() {
+            inverse.corners[home] = (position, (3 - shift) % 3);
+        }
+        for (position, &(home, flipped)) in self.edges.iter().enumerate() {
+            inverse.edges[home] = (position, flipped);
+        }
+        for (position, &home) in self.centers.iter().enumerate() {
+            inverse.centers[home] = position;
+        }
+        inverse
+    }
+}
+
+impl Cube {
+    /// The solved cube with the same colors, that this state is read against
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{color_scheme::ColorScheme, cube::Cube, moves::Algorithm};
+    ///
+    /// let cube = Cube::new_with_scheme(ColorScheme::wca()).apply(Algorithm::from("R U").unwrap());
+    ///
+    /// assert_eq!(cube.solved(), Cube::new());
+    /// ```
+    #[must_use]
+    pub fn solved(&self) -> Self {
+        let scheme = ColorScheme::of(self);
+        let held = Side::ALL
+            .into_iter()
+            .find_map(|front| scheme.held(Side::Yellow, front).ok())
+            .unwrap_or(scheme);
+        Self::new_with_scheme(held)
+    }
+
+    /// How the pieces of this state were moved from its [solved](Self::solved) cube
+    fn permutation(&self) -> Permutation {
+        Permutation::between(&self.solved(), self)
+            .expect("every piece of a cube is a piece of its solved cube")
+    }
+
+    /// The state reached by doing what made this state, then what made `other`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let a = Cube::new().apply(Algorithm::from("R U").unwrap());
+    /// let b = Cube::new().apply(Algorithm::from("F' y").unwrap());
+    ///
+    /// assert_eq!(
+    ///     a.compose(&b),
+    ///     Cube::new().apply(Algorithm::from("R U F' y").unwrap())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn compose(&self, other: &Self) -> Self {
+        other.permutation().apply(self)
+    }
+
+    /// The state that undoes this one, so composing them gives the solved cube
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let sexy = Cube::new().apply(Algorithm::sexy());
+    ///
+    /// assert_eq!(sexy.inverse(), Cube::new().apply(Algorithm::sexy().inverse()));
+    /// assert_eq!(sexy.compose(&sexy.inverse()), Cube::new());
+    /// ```
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        self.permutation().inverse().apply(&self.solved())
+    }
+
+    /// The state that takes this one to `other`, so that `self.compose(&difference) == other`
+    ///
+    /// Solving the difference finds an algorithm that goes from this state to `other`. Returns
+    /// `None` when the two cubes aren't made of the same pieces, like when their colors differ
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let a = Cube::new().apply(Algorithm::from("R U R'").unwrap());
+    /// let b = Cube::new().apply(Algorithm::from("R U2 R'").unwrap());
+    /// let difference = a.difference(&b).unwrap();
+    ///
+    /// assert_eq!(difference, Cube::new().apply(Algorithm::from("R U R'").unwrap()));
+    /// assert_eq!(a.compose(&difference), b);
+    /// ```
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Option<Self> {
+        Permutation::between(self, other).map(|permutation| permutation.apply(&self.solved()))
+    }
+
+    /// Composes this state with itself `n` times, or composes its inverse when `n` is negative
+    ///
+    /// This takes about `log2(n)` compositions, so large powers are quick to find
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{cube::Cube, moves::Algorithm};
+    ///
+    /// let sexy = Cube::new().apply(Algorithm::sexy());
+    ///
+    /// assert_eq!(sexy.pow(6_000_000), Cube::new());
+    /// assert_eq!(sexy.pow(-1), sexy.inverse());
+    /// ```
+    #[must_use]
+    pub fn pow(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { self.clone() };
+        let mut result = self.solved();
+        let mut n = n.unsigned_abs();
+        while n > 0 {
+            if n % 2 == 1 {
+                result = result.compose(&base);
+            }
+            base = base.compose(&base);
+            n /= 2;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod group_tests {
+    use super::*;
+    use crate::moves::Algorithm;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn state(scheme: ColorScheme, moves: &str) -> Cube {
+        Cube::new_with_scheme(scheme).apply(Algorithm::from(moves).unwrap())
+    }
+
+    #[test]
+    fn compose_matches_applying_moves() {
+        let scheme = ColorScheme::yellow_top();
+        let a = state(scheme, "R U2 F' x 2R Dw'");
+        let b = state(scheme, "L B2 z' 3F D' y");
+
+        assert_eq!(
+            a.compose(&b),
+            state(scheme, "R U2 F' x 2R Dw' L B2 z' 3F D' y")
+        );
+    }
+
+    #[test]
+    fn inverse_matches_inverse_moves() {
+        let moves = Algorithm::from("R U2 F' x 2R Dw' L B2 z'").unwrap();
+        let cube = Cube::new().apply(moves.clone());
+
+        assert_eq!(cube.inverse(), Cube::new().apply(moves.inverse()));
+        assert_eq!(cube.inverse().compose(&cube), Cube::new());
+    }
+
+    #[test]
+    fn other_colors_are_read_against_their_own_solved_cube() {
+        let scheme = ColorScheme::japanese();
+        let solved = Cube::new().solved();
+        let japanese = state(scheme, "").solved();
+        let cube = japanese.apply(Algorithm::from("R U' x").unwrap());
+
+        assert_eq!(solved, Cube::new());
+        assert_eq!(japanese.solved(), japanese);
+        assert_eq!(
+            cube.compose(&cube.inverse()),
+            japanese,
+            "composing with the inverse gives the solved cube"
+        );
+        assert_eq!(Cube::new().difference(&japanese), None);
+    }
+
+    #[test]
+    fn difference_goes_between_states() {
+        let scheme = ColorScheme::wca();
+        let a = state(scheme, "R U2 F' x 2R");
+        let b = state(scheme, "Dw' L B2 z' 3F D' y");
+        let difference = a.difference(&b).unwrap();
+
+        assert_eq!(a.compose(&difference), b);
+        assert_eq!(b.difference(&a), Some(difference.inverse()));
+    }
+
+    #[test]
+    fn powers_match_repeated_moves() {
+        let moves = Algorithm::from("R U2 F' x 2R Dw'").unwrap();
+        let cube = Cube::new().apply(moves.clone());
+
+        for n in [-7, -1, 0, 1, 2, 5, 13] {
+            assert_eq!(cube.pow(n), Cube::new().apply(moves.pow(n)), "power {n}");
+        }
+    }
+}
@@ -18,10 +18,32 @@
 //!
 //! assert!(solution.solves(&scramble));
 //! ```
+//!
+//! # Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for algorithms, moves and cubes
 
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 
 // https://jperm.net/3x3/moves
-mod cube;
+pub mod blind;
+pub mod color_scheme;
+pub mod commutators;
+mod coordinates;
+pub mod cube;
+pub mod cube2;
+pub mod encoding;
+pub mod execution;
+pub mod facelets;
+pub mod fmc;
+pub mod group;
+pub mod mask;
 
 pub mod moves;
+pub mod nxn;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod solvers;
+pub mod stickers;
+pub mod svg;
+pub mod terminal;
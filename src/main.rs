@@ -0,0 +1,387 @@
+//! The `rubiks-moves` command, for everyday jobs with algorithms
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+mod repl;
+
+use std::{
+    fmt::Write,
+    io::{self, BufRead},
+    process::ExitCode,
+};
+
+use rubiks_moves::{
+    cube::Cube,
+    moves::{Algorithm, Metric, Mirror, MoveParseError},
+    terminal::{self, Style},
+};
+use thiserror::Error;
+
+const USAGE: &str = "Usage: rubiks-moves [--json] <command> [options] [moves...]
+
+Commands:
+    invert      Prints the moves that undo an algorithm
+    simplify    Combines and cancels moves that are next to each other
+    mirror      Mirrors an algorithm, across M unless --plane s or --plane e is given
+    count       Counts the moves of an algorithm in HTM, QTM and ETM
+    verify      Checks that a solution solves a scramble, given as two quoted arguments
+    print       Prints the cube after an algorithm, with --style emoji, ansi or letters
+    order       Counts how many times an algorithm is repeated to get back to solved
+    repl        Starts a shell for trying out moves on a cube, with --style like print
+
+When no moves are given, every line of stdin is read as its own algorithm, and verify reads the
+scramble and the solution from the first two lines. With --json, every result is printed as a
+JSON object on its own line.";
+
+/// Occurs when the command can't do what it was asked
+#[derive(Debug, Error, PartialEq, Eq)]
+enum CliError {
+    #[error("no command was given")]
+    MissingCommand,
+    #[error("unknown command {0}")]
+    UnknownCommand(String),
+    #[error("unknown option {0}")]
+    UnknownOption(String),
+    #[error("{0} needs a value")]
+    MissingValue(String),
+    #[error("{1} is not a value for {0}")]
+    UnknownValue(String, String),
+    #[error("verify needs a scramble and a solution")]
+    VerifyNeedsTwo,
+    #[error("repl reads its moves as it runs, so it takes no moves and no --json")]
+    ReplArguments,
+    #[error("could not read stdin: {0}")]
+    Stdin(String),
+    #[error(transparent)]
+    Parse(#[from] MoveParseError),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Command {
+    Help,
+    Invert,
+    Simplify,
+    Mirror(Mirror),
+    Count,
+    Verify,
+    Print(Style),
+    Order,
+    Repl(Style),
+}
+
+/// What was asked for on the command line
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    command: Command,
+    json: bool,
+    /// The moves given as arguments, which are read from stdin when there are none
+    moves: Vec<String>,
+}
+
+/// What the command prints, and whether it succeeded
+#[derive(Debug, PartialEq, Eq)]
+struct Output {
+    text: String,
+    success: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, CliError> {
+    let mut json = false;
+    let mut command = None;
+    let mut moves = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(|value| value.to_lowercase())
+                .ok_or_else(|| CliError::MissingValue(arg.clone()))
+        };
+        match (arg.as_str(), command) {
+            ("--json", _) => json = true,
+            ("--help" | "-h", _) => command = Some(Command::Help),
+            ("--plane", Some(Command::Mirror(_))) => {
+                let plane = match value()?.as_str() {
+                    "m" => Mirror::M,
+                    "s" => Mirror::S,
+                    "e" => Mirror::E,
+                    other => return Err(CliError::UnknownValue(arg.clone(), other.to_string())),
+                };
+                command = Some(Command::Mirror(plane));
+            }
+            ("--style", Some(Command::Print(_) | Command::Repl(_))) => {
+                let style = match value()?.as_str() {
+                    "emoji" => Style::Emoji,
+                    "ansi" => Style::Ansi,
+                    "letters" => Style::Letters,
+                    other => return Err(CliError::UnknownValue(arg.clone(), other.to_string())),
+                };
+                command = command.map(|command| match command {
+                    Command::Repl(_) => Command::Repl(style),
+                    _ => Command::Print(style),
+                });
+            }
+            (option, _) if option.starts_with("--") => {
+                return Err(CliError::UnknownOption(option.to_string()))
+            }
+            (name, None) => {
+                command = Some(match name {
+                    "help" => Command::Help,
+                    "invert" => Command::Invert,
+                    "simplify" => Command::Simplify,
+                    "mirror" => Command::Mirror(Mirror::M),
+                    "count" => Command::Count,
+                    "verify" => Command::Verify,
+                    "print" => Command::Print(Style::Emoji),
+                    "order" => Command::Order,
+                    "repl" => Command::Repl(Style::Emoji),
+                    _ => return Err(CliError::UnknownCommand(name.to_string())),
+                });
+            }
+            (moves_arg, Some(_)) => moves.push(moves_arg.to_string()),
+        }
+    }
+    let command = command.ok_or(CliError::MissingCommand)?;
+    if matches!(command, Command::Repl(_)) && (json || !moves.is_empty()) {
+        return Err(CliError::ReplArguments);
+    }
+    Ok(Options {
+        command,
+        json,
+        moves,
+    })
+}
+
+/// Runs the command on every algorithm, reading them from `stdin` if none were given as arguments
+fn run(options: &Options, stdin: impl BufRead) -> Result<Output, CliError> {
+    let lines = if options.moves.is_empty() && options.command != Command::Help {
+        stdin
+            .lines()
+            .map(|line| line.map(|line| line.trim().to_string()))
+            .filter(|line| line.as_ref().map_or(true, |line| !line.is_empty()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| CliError::Stdin(error.to_string()))?
+    } else if options.command == Command::Verify {
+        options.moves.clone()
+    } else {
+        vec![options.moves.join(" ")]
+    };
+
+    match options.command {
+        Command::Help => Ok(Output {
+            text: USAGE.to_string(),
+            success: true,
+        }),
+        Command::Verify => {
+            let [scramble, solution] = lines.as_slice() else {
+                return Err(CliError::VerifyNeedsTwo);
+            };
+            let solves = Algorithm::from(solution)?.solves(&Algorithm::from(scramble)?);
+            let text = if options.json {
+                format!(
+                    r#"{{"scramble":{},"solution":{},"solves":{solves}}}"#,
+                    json_string(scramble),
+                    json_string(solution)
+                )
+            } else if solves {
+                "solved".to_string()
+            } else {
+                "not solved".to_string()
+            };
+            Ok(Output {
+                text,
+                success: solves,
+            })
+        }
+        command => {
+            let results = lines
+                .iter()
+                .map(|line| run_one(command, options.json, line))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Output {
+                text: results.join("\n"),
+                success: true,
+            })
+        }
+    }
+}
+
+fn run_one(command: Command, json: bool, input: &str) -> Result<String, CliError> {
+    let alg = Algorithm::from(input)?;
+    let (text, value) = match command {
+        Command::Invert => with_alg(&alg.inverse()),
+        Command::Simplify => with_alg(&alg.simplify()),
+        Command::Mirror(plane) => with_alg(&alg.mirror(plane)),
+        Command::Count => {
+            let [htm, qtm, etm] = [Metric::Htm, Metric::Qtm, Metric::Etm].map(|m| alg.length(m));
+            (
+                format!("{htm} HTM, {qtm} QTM, {etm} ETM"),
+                format!(r#""htm":{htm},"qtm":{qtm},"etm":{etm}"#),
+            )
+        }
+        Command::Print(style) => {
+            let net = terminal::net(&Cube::new().apply(alg), style);
+            let value = format!(r#""cube":{}"#, json_string(&net));
+            (net, value)
+        }
+        Command::Order => {
+            let order = alg.order();
+            (order.to_string(), format!(r#""order":{order}"#))
+        }
+        Command::Help | Command::Verify | Command::Repl(_) => {
+            unreachable!("these don't run one algorithm at a time")
+        }
+    };
+    Ok(if json {
+        format!(r#"{{"input":{},{value}}}"#, json_string(input))
+    } else {
+        text
+    })
+}
+
+fn with_alg(alg: &Algorithm) -> (String, String) {
+    let text = alg.to_string();
+    let value = format!(r#""output":{}"#, json_string(&text));
+    (text, value)
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                write!(out, "\\u{:04x}", u32::from(c)).expect("writing to a string can't fail");
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Prints an error with the usage, for when the command line couldn't be followed
+fn usage_error(error: &CliError) -> ExitCode {
+    eprintln!("error: {error}\n\n{USAGE}");
+    ExitCode::from(2)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(error) => return usage_error(&error),
+    };
+    if let Command::Repl(style) = options.command {
+        return match repl::run(style, io::stdin().lock(), io::stdout().lock()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: {error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    match run(&options, io::stdin().lock()) {
+        Ok(output) => {
+            println!("{}", output.text);
+            if output.success {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(error) => usage_error(&error),
+    }
+}
+
+#[cfg(test)]
+mod main_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(' ').map(ToString::to_string).collect()
+    }
+
+    fn output(command: &str, stdin: &str) -> Output {
+        let options = parse_args(&args(command)).unwrap();
+        run(&options, stdin.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn inverts_arguments() {
+        let actual = output("invert R U R' U'", "");
+
+        assert_str_eq!(actual.text, "U R U' R'");
+    }
+
+    #[test]
+    fn reads_each_line_of_stdin() {
+        let actual = output("simplify", "R R\n\nU U'\nF2 F\n");
+
+        assert_str_eq!(actual.text, "R2\n\nF'");
+    }
+
+    #[test]
+    fn mirrors_across_planes() {
+        assert_str_eq!(output("mirror R U", "").text, "L' U'");
+        assert_str_eq!(output("mirror --plane s R U", "").text, "R' U'");
+    }
+
+    #[test]
+    fn counts_in_json() {
+        let actual = output("--json count R U2 y", "");
+
+        assert_str_eq!(actual.text, r#"{"input":"R U2 y","htm":2,"qtm":3,"etm":3}"#);
+    }
+
+    #[test]
+    fn verifies_solutions() {
+        let solved = parse_args(&["verify".to_string(), "R U".to_string(), "U' R'".to_string()]);
+        let actual = run(&solved.unwrap(), &b""[..]).unwrap();
+
+        assert!(actual.success);
+        assert!(!output("verify", "R U\nR' U'").success);
+    }
+
+    #[test]
+    fn prints_the_cube() {
+        let actual = output("--json print --style letters R", "");
+
+        assert_str_eq!(
+            actual.text,
+            r#"{"input":"R","cube":"   YYR\n   YYR\n   YYR\nBBBRRWGGGYOO\nBBBRRWGGGYOO\nBBBRRWGGGYOO\n   WWO\n   WWO\n   WWO"}"#
+        );
+    }
+
+    #[test]
+    fn finds_the_order() {
+        assert_str_eq!(output("order R U R' U'", "").text, "6");
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(
+            parse_args(&args("twist R")),
+            Err(CliError::UnknownCommand("twist".to_string()))
+        );
+        assert_eq!(
+            parse_args(&args("print --style pixels")),
+            Err(CliError::UnknownValue(
+                "--style".to_string(),
+                "pixels".to_string()
+            ))
+        );
+        let options = parse_args(&args("invert R Q")).unwrap();
+        assert!(matches!(run(&options, &b""[..]), Err(CliError::Parse(_))));
+        assert_eq!(
+            parse_args(&args("--json repl")),
+            Err(CliError::ReplArguments)
+        );
+        assert_eq!(
+            parse_args(&args("repl --style ansi R U")),
+            Err(CliError::ReplArguments)
+        );
+        assert!(parse_args(&args("repl --style ansi")).is_ok());
+    }
+}
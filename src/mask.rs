@@ -0,0 +1,247 @@
+//! Greys out the stickers that don't matter, for training diagrams
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    cube::Face,
+    stickers::{Sticker, Stickers},
+    terminal::{self, Style},
+};
+
+/// A step of solving, whose mask only shows the pieces that step is about
+///
+/// Every stage is solved with the cross on D and the last layer on U
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stage {
+    /// The D edges and every center
+    Cross,
+    /// Everything but the U layer
+    F2L,
+    /// Only the U face
+    OLL,
+    /// The whole U layer
+    PLL,
+    /// The U edges and every center
+    ELL,
+    /// The L and R blocks under the U layer and the U corners, for Roux
+    CMLL,
+}
+
+/// A set of stickers that are hidden when a cube is drawn
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     mask::{Mask, Stage},
+///     moves::Algorithm,
+///     terminal::{self, Style},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R U R' U R U2 R'").unwrap());
+/// let mask = Mask::stage(Stage::OLL, 3);
+///
+/// assert_eq!(
+///     terminal::masked_net(&cube, Style::Letters, &mask),
+///     "   RYY
+///    YYY
+///    GYO
+/// ------------
+/// ------------
+/// ------------
+///    ---
+///    ---
+///    ---"
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Mask {
+    hidden: HashSet<Sticker>,
+}
+
+impl Mask {
+    /// A mask that hides exactly these stickers
+    pub fn hiding(stickers: impl IntoIterator<Item = Sticker>) -> Self {
+        Self {
+            hidden: stickers.into_iter().collect(),
+        }
+    }
+
+    /// A mask for a cube with `size` layers that hides every sticker except these
+    pub fn showing(size: usize, stickers: impl IntoIterator<Item = Sticker>) -> Self {
+        let shown: HashSet<Sticker> = stickers.into_iter().collect();
+        Self::hiding(
+            all_stickers(size)
+                .into_iter()
+                .filter(|sticker| !shown.contains(sticker)),
+        )
+    }
+
+    /// The mask for a cube with `size` layers that shows only the pieces `stage` is about
+    ///
+    /// # Panics
+    ///
+    /// This panics if `size` is 0
+    #[must_use]
+    pub fn stage(stage: Stage, size: usize) -> Self {
+        let max = i32::try_from(size - 1).expect("cubes are not that big");
+        Self::showing(
+            size,
+            all_stickers(size).into_iter().filter(|&sticker| {
+                let [x, y, _] = sticker.location(size);
+                let faces = sticker.piece_faces(size).len();
+                let center = faces == 1;
+                match stage {
+                    Stage::Cross => center || (faces == 2 && y == -max),
+                    Stage::F2L => y != max,
+                    Stage::OLL => sticker.face == Face::U,
+                    Stage::PLL => y == max,
+                    Stage::ELL => center || (faces == 2 && y == max),
+                    Stage::CMLL => (x.abs() == max && y != max) || (faces == 3 && y == max),
+                }
+            }),
+        )
+    }
+
+    /// Determines if a sticker is greyed out
+    #[must_use]
+    pub fn hides(&self, sticker: Sticker) -> bool {
+        self.hidden.contains(&sticker)
+    }
+
+    /// Pairs a cube with this mask, so that it prints with the hidden stickers greyed out
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{
+    ///     cube2::Cube2,
+    ///     mask::{Mask, Stage},
+    /// };
+    ///
+    /// let mask = Mask::stage(Stage::PLL, 2);
+    ///
+    /// assert_eq!(
+    ///     format!("{}", mask.over(&Cube2::new())),
+    ///     "⬛⬛🟨🟨⬛⬛⬛⬛
+    /// ⬛⬛🟨🟨⬛⬛⬛⬛
+    /// 🟦🟦🟥🟥🟩🟩🟧🟧
+    /// 🔲🔲🔲🔲🔲🔲🔲🔲
+    /// ⬛⬛🔲🔲⬛⬛⬛⬛
+    /// ⬛⬛🔲🔲⬛⬛⬛⬛"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn over<'a, C: Stickers>(&'a self, cube: &'a C) -> Masked<'a, C> {
+        Masked { cube, mask: self }
+    }
+}
+
+fn all_stickers(size: usize) -> Vec<Sticker> {
+    Face::ALL
+        .into_iter()
+        .flat_map(|face| {
+            (0..size).flat_map(move |row| (0..size).map(move |col| Sticker { face, row, col }))
+        })
+        .collect()
+}
+
+/// A cube seen through a [`Mask`], made by [`Mask::over`]
+///
+/// Like the cubes themselves, `{}` prints an emoji net and `{:#}` prints an ANSI net
+#[derive(Debug, Clone, Copy)]
+pub struct Masked<'a, C> {
+    cube: &'a C,
+    mask: &'a Mask,
+}
+
+impl<C: Stickers> Display for Masked<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let style = if f.alternate() {
+            Style::Ansi
+        } else {
+            Style::Emoji
+        };
+        write!(f, "{}", terminal::masked_net(self.cube, style, self.mask))
+    }
+}
+
+#[cfg(test)]
+mod mask_tests {
+    use super::*;
+    use crate::{cube::Cube, nxn::NxNCube};
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn shown(stage: Stage, size: usize) -> usize {
+        let mask = Mask::stage(stage, size);
+        all_stickers(size)
+            .into_iter()
+            .filter(|&sticker| !mask.hides(sticker))
+            .count()
+    }
+
+    #[test]
+    fn stages_show_their_pieces() {
+        assert_eq!(shown(Stage::Cross, 3), 4 * 2 + 6);
+        assert_eq!(shown(Stage::F2L, 3), 54 - 21);
+        assert_eq!(shown(Stage::OLL, 3), 9);
+        assert_eq!(shown(Stage::PLL, 3), 21);
+        assert_eq!(shown(Stage::ELL, 3), 4 * 2 + 6);
+        assert_eq!(shown(Stage::CMLL, 3), 2 * 13 + 4 * 3);
+    }
+
+    #[test]
+    fn stages_work_on_bigger_cubes() {
+        assert_eq!(shown(Stage::OLL, 4), 16);
+        assert_eq!(shown(Stage::PLL, 4), 16 + 4 * 4);
+        assert_eq!(shown(Stage::F2L, 5), 150 - 25 - 4 * 5);
+    }
+
+    #[test]
+    fn custom_masks() {
+        let sticker = Sticker {
+            face: Face::F,
+            row: 1,
+            col: 1,
+        };
+        let hiding = Mask::hiding([sticker]);
+        let showing = Mask::showing(3, [sticker]);
+
+        assert!(hiding.hides(sticker));
+        assert!(!showing.hides(sticker));
+        assert_eq!(
+            all_stickers(3)
+                .into_iter()
+                .filter(|&s| showing.hides(s))
+                .count(),
+            53
+        );
+    }
+
+    #[test]
+    fn masked_display() {
+        let mask = Mask::stage(Stage::F2L, 3);
+        let cube = Cube::new();
+        let actual = format!("{}", mask.over(&cube));
+        let expected = "⬛⬛⬛🔲🔲🔲⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🔲🔲🔲⬛⬛⬛⬛⬛⬛
+⬛⬛⬛🔲🔲🔲⬛⬛⬛⬛⬛⬛
+🔲🔲🔲🔲🔲🔲🔲🔲🔲🔲🔲🔲
+🟦🟦🟦🟥🟥🟥🟩🟩🟩🟧🟧🟧
+🟦🟦🟦🟥🟥🟥🟩🟩🟩🟧🟧🟧
+⬛⬛⬛⬜⬜⬜⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬜⬜⬜⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬜⬜⬜⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn masked_ansi_is_grey() {
+        let mask = Mask::stage(Stage::Cross, 4);
+        let actual = format!("{:#}", mask.over(&NxNCube::new(4)));
+
+        assert!(actual.contains("\x1b[48;2;128;128;128m"));
+    }
+}
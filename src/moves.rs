@@ -1,10 +1,18 @@
 //! Representations for algorithms taht can be performed
-use std::{fmt::Display, ops::Add};
+use std::{
+    fmt::Display,
+    ops::{Add, Mul},
+};
 
 use itertools::Itertools;
 use nom::{
-    branch::alt, bytes::complete::tag, character::complete::space1, combinator::map,
-    multi::separated_list0, IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, space1},
+    combinator::{eof, map, map_res, opt, peek, verify},
+    multi::separated_list0,
+    sequence::{pair, terminated},
+    IResult,
 };
 use thiserror::Error;
 
@@ -15,7 +23,7 @@ use crate::cube::Cube;
 /// - A clockwise quarter turn (like U or F) is denoted as `U(1)` or `F(1)`
 /// - A counter-clockwise turn (like U' or F') is denoted as `U(3)` or `F(3)`
 /// - A double turn (like U2 or F2) is denoted as `U(2)` or `F(2)`
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum FaceTurn {
     U(u8),
     D(u8),
@@ -25,10 +33,54 @@ pub enum FaceTurn {
     R(u8),
 }
 
+/// Defines all possible whole cube rotations
+///
+/// - `X` follows the direction of R, so `X(1)` is x and `X(3)` is x'
+/// - `Y` follows the direction of U
+/// - `Z` follows the direction of F
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Rotation {
+    X(u8),
+    Y(u8),
+    Z(u8),
+}
+
+/// Defines turns of inner layers, or of several layers at once, like those used on bigger cubes
+///
+/// Layers are counted from the face being turned, with the outer layer being `1`
+///
+/// - Turning only the second layer from R (like 2R) is `LayerTurn { turn: FaceTurn::R(1), depth: 2, wide: false }`
+/// - Turning the outer two layers of R (like Rw) is `LayerTurn { turn: FaceTurn::R(1), depth: 2, wide: true }`
+/// - Turning the outer three layers of U counter-clockwise (like 3Uw') is `LayerTurn { turn: FaceTurn::U(3), depth: 3, wide: true }`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct LayerTurn {
+    /// Which face the layers are counted from, and how far they are turned
+    pub turn: FaceTurn,
+    /// The layer that is turned, or the innermost layer that is turned for wide turns
+    pub depth: u8,
+    /// Whether every layer between the face and `depth` is turned too
+    pub wide: bool,
+}
+
+/// Defines all possible turns of the middle layers of a 3x3
+///
+/// - `M` is the layer between L and R, and follows the direction of L
+/// - `E` is the layer between U and D, and follows the direction of D
+/// - `S` is the layer between F and B, and follows the direction of F
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum SliceTurn {
+    M(u8),
+    E(u8),
+    S(u8),
+}
+
 /// A wrapper type that defines any possible move, including face turns, wide turn, cube rotations, and slice moves
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum Move {
     FaceTurn(FaceTurn),
+    Rotation(Rotation),
+    Layer(LayerTurn),
+    Slice(SliceTurn),
 }
 
 /// Represents a series of moves you can perform on a cube
@@ -40,11 +92,34 @@ pub enum Move {
 ///
 /// let alg = Algorithm::from("R U R' U'").unwrap();
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, PartialOrd, Ord, Hash)]
 pub struct Algorithm {
     pub(crate) moves: Vec<Move>,
 }
 
+/// The planes an [`Algorithm`] can be mirrored across, named after the slice that lies in each one
+///
+/// - `M` swaps L and R, like turning a right-handed alg into a left-handed one
+/// - `S` swaps F and B
+/// - `E` swaps U and D
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Mirror {
+    M,
+    S,
+    E,
+}
+
+/// The ways of counting how long an [`Algorithm`] is
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Metric {
+    /// Half turn metric, where every face or layer turn counts once and rotations are free
+    Htm,
+    /// Quarter turn metric, where half turns count twice and rotations are free
+    Qtm,
+    /// Execution turn metric, where every move counts once, rotations included
+    Etm,
+}
+
 /// Occurs when a string cannot be read as a [`Algorithm`]
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum MoveParseError {
@@ -79,6 +154,157 @@ impl FaceTurn {
             Self::R(t) => Self::R(inv(*t)),
         }
     }
+
+    /// This creates the turn that does the same thing from the other side of a mirror
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::{FaceTurn, Mirror};
+    ///
+    /// assert_eq!(FaceTurn::R(1).mirror(Mirror::M), FaceTurn::L(3));
+    /// assert_eq!(FaceTurn::U(2).mirror(Mirror::M), FaceTurn::U(2));
+    /// ```
+    #[must_use]
+    pub const fn mirror(&self, mirror: Mirror) -> Self {
+        match (mirror, self.inverse()) {
+            (Mirror::M, Self::L(t)) => Self::R(t),
+            (Mirror::M, Self::R(t)) => Self::L(t),
+            (Mirror::S, Self::F(t)) => Self::B(t),
+            (Mirror::S, Self::B(t)) => Self::F(t),
+            (Mirror::E, Self::U(t)) => Self::D(t),
+            (Mirror::E, Self::D(t)) => Self::U(t),
+            (_, turn) => turn,
+        }
+    }
+}
+
+impl Rotation {
+    /// This creates the rotation that will undo a given rotation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::Rotation;
+    ///
+    /// let x = Rotation::X(1);
+    /// let x_rev = Rotation::X(3);
+    ///
+    /// assert_eq!(x.inverse(), x_rev);
+    /// ```
+    #[must_use]
+    pub const fn inverse(&self) -> Self {
+        const fn inv(t: u8) -> u8 {
+            (t * 3) % 4
+        }
+        match self {
+            Self::X(t) => Self::X(inv(*t)),
+            Self::Y(t) => Self::Y(inv(*t)),
+            Self::Z(t) => Self::Z(inv(*t)),
+        }
+    }
+
+    /// This creates the rotation that does the same thing from the other side of a mirror
+    ///
+    /// Rotations around the axis at right angles to the mirror are left alone, since the mirror
+    /// doesn't change which way they turn
+    #[must_use]
+    pub const fn mirror(&self, mirror: Mirror) -> Self {
+        match (mirror, self) {
+            (Mirror::M, Self::X(_)) | (Mirror::E, Self::Y(_)) | (Mirror::S, Self::Z(_)) => *self,
+            _ => self.inverse(),
+        }
+    }
+}
+
+impl LayerTurn {
+    /// This creates the turn that will undo a given turn
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::{FaceTurn, LayerTurn};
+    ///
+    /// let rw = LayerTurn { turn: FaceTurn::R(1), depth: 2, wide: true };
+    /// let rw_rev = LayerTurn { turn: FaceTurn::R(3), depth: 2, wide: true };
+    ///
+    /// assert_eq!(rw.inverse(), rw_rev);
+    /// ```
+    #[must_use]
+    pub const fn inverse(&self) -> Self {
+        Self {
+            turn: self.turn.inverse(),
+            ..*self
+        }
+    }
+
+    /// This creates the turn that does the same thing from the other side of a mirror
+    #[must_use]
+    pub const fn mirror(&self, mirror: Mirror) -> Self {
+        Self {
+            turn: self.turn.mirror(mirror),
+            ..*self
+        }
+    }
+}
+
+impl SliceTurn {
+    /// This creates the turn that will undo a given turn
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::SliceTurn;
+    ///
+    /// assert_eq!(SliceTurn::M(1).inverse(), SliceTurn::M(3));
+    /// ```
+    #[must_use]
+    pub const fn inverse(&self) -> Self {
+        const fn inv(t: u8) -> u8 {
+            (t * 3) % 4
+        }
+        match self {
+            Self::M(t) => Self::M(inv(*t)),
+            Self::E(t) => Self::E(inv(*t)),
+            Self::S(t) => Self::S(inv(*t)),
+        }
+    }
+
+    /// This creates the turn that does the same thing from the other side of a mirror
+    ///
+    /// Like rotations, the slice lying in the mirror is left alone, and the others turn the
+    /// other way
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::{Mirror, SliceTurn};
+    ///
+    /// assert_eq!(SliceTurn::M(1).mirror(Mirror::M), SliceTurn::M(1));
+    /// assert_eq!(SliceTurn::M(1).mirror(Mirror::S), SliceTurn::M(3));
+    /// ```
+    #[must_use]
+    pub const fn mirror(&self, mirror: Mirror) -> Self {
+        match (mirror, self) {
+            (Mirror::M, Self::M(_)) | (Mirror::E, Self::E(_)) | (Mirror::S, Self::S(_)) => *self,
+            _ => self.inverse(),
+        }
+    }
+
+    /// The same turn as a [`LayerTurn`] of the second layer from L, D, or F
+    #[must_use]
+    pub const fn layer(&self) -> LayerTurn {
+        let turn = match self {
+            Self::M(t) => FaceTurn::L(*t),
+            Self::E(t) => FaceTurn::D(*t),
+            Self::S(t) => FaceTurn::F(*t),
+        };
+        LayerTurn {
+            turn,
+            depth: 2,
+            wide: false,
+        }
+    }
 }
 
 impl Move {
@@ -98,6 +324,34 @@ impl Move {
     pub const fn inverse(&self) -> Self {
         match self {
             Self::FaceTurn(t) => Self::FaceTurn(t.inverse()),
+            Self::Rotation(r) => Self::Rotation(r.inverse()),
+            Self::Layer(l) => Self::Layer(l.inverse()),
+            Self::Slice(s) => Self::Slice(s.inverse()),
+        }
+    }
+
+    /// This creates the move that does the same thing from the other side of a mirror
+    #[must_use]
+    pub const fn mirror(&self, mirror: Mirror) -> Self {
+        match self {
+            Self::FaceTurn(t) => Self::FaceTurn(t.mirror(mirror)),
+            Self::Rotation(r) => Self::Rotation(r.mirror(mirror)),
+            Self::Layer(l) => Self::Layer(l.mirror(mirror)),
+            Self::Slice(s) => Self::Slice(s.mirror(mirror)),
+        }
+    }
+
+    /// How much this move counts towards the length of an [`Algorithm`]
+    const fn length(self, metric: Metric) -> usize {
+        let amount = match self {
+            Self::FaceTurn(t) | Self::Layer(LayerTurn { turn: t, .. }) => t.face().1,
+            Self::Slice(s) => s.layer().turn.face().1,
+            Self::Rotation(_) => return matches!(metric, Metric::Etm) as usize,
+        };
+        match (metric, amount) {
+            (Metric::Qtm, 2) => 2,
+            (_, 0) => 0,
+            _ => 1,
         }
     }
 }
@@ -152,7 +406,10 @@ impl Algorithm {
     ///
     /// # Errors
     ///
-    /// This errors when it is not given a space seperated list of single face turns e.g. U, F', or D2
+    /// This errors when it is not given a space seperated list of single face turns e.g. U, F', or D2,
+    /// rotations e.g. x, y', or z2, layer turns e.g. Rw, r, 2R', or 3Uw2, or slice turns e.g. M'
+    ///
+    /// Lowercase turns like r are read as the same turn as Rw, so they are displayed as Rw
     ///
     /// # Example
     ///
@@ -164,7 +421,15 @@ impl Algorithm {
     pub fn from(s: &str) -> Result<Self, MoveParseError> {
         let (s, m) = separated_list0(
             space1,
-            alt((u_moves, d_moves, f_moves, b_moves, l_moves, r_moves)),
+            alt((
+                map(layer_turns, Move::Layer),
+                map(whole(alt((m_slices, e_slices, s_slices))), Move::Slice),
+                map(
+                    alt((u_moves, d_moves, f_moves, b_moves, l_moves, r_moves)),
+                    Move::FaceTurn,
+                ),
+                map(alt((x_rotations, y_rotations, z_rotations)), Move::Rotation),
+            )),
         )(s)?;
         if s.is_empty() {
             Ok(m.into())
@@ -194,6 +459,43 @@ impl Algorithm {
         }
     }
 
+    /// Creates the [`Algorithm`] that does the same thing from the other side of a mirror
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::{Algorithm, Mirror};
+    ///
+    /// let right = Algorithm::from("R U R' U'").unwrap();
+    /// let left = Algorithm::from("L' U' L U").unwrap();
+    ///
+    /// assert_eq!(right.mirror(Mirror::M), left);
+    /// ```
+    #[must_use]
+    pub fn mirror(&self, mirror: Mirror) -> Self {
+        Self {
+            moves: self.moves.iter().map(|m| m.mirror(mirror)).collect(),
+        }
+    }
+
+    /// Counts how many moves are in an [`Algorithm`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::{Algorithm, Metric};
+    ///
+    /// let alg = Algorithm::from("y R U2 R'").unwrap();
+    ///
+    /// assert_eq!(alg.length(Metric::Htm), 3);
+    /// assert_eq!(alg.length(Metric::Qtm), 4);
+    /// assert_eq!(alg.length(Metric::Etm), 4);
+    /// ```
+    #[must_use]
+    pub fn length(&self, metric: Metric) -> usize {
+        self.moves.iter().map(|&m| m.length(metric)).sum()
+    }
+
     /// Combines two [`Algorithm`]s in the form of ABA'B'
     ///
     /// # Example
@@ -232,7 +534,36 @@ impl Algorithm {
         self.clone() + other + &self.inverse()
     }
 
+    /// Repeats an [`Algorithm`] `n` times, or repeats its inverse when `n` is negative
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::moves::Algorithm;
+    ///
+    /// let sune = Algorithm::from("R U R' U R U2 R'").unwrap();
+    ///
+    /// assert_eq!(Algorithm::from("R U").unwrap().pow(2), Algorithm::from("R U R U").unwrap());
+    /// assert_eq!(sune.pow(-1), sune.inverse());
+    /// assert_eq!(sune.pow(0), Algorithm::new());
+    /// ```
+    #[must_use]
+    pub fn pow(&self, n: i32) -> Self {
+        let moves = if n < 0 {
+            self.inverse().moves
+        } else {
+            self.moves.clone()
+        };
+        Self {
+            moves: moves.repeat(n.unsigned_abs() as usize),
+        }
+    }
+
     /// A sample [`Algorithm`] that is used often in speedcubing. Equvalent to R U R' U'
+    ///
+    /// # Panics
+    ///
+    /// This never panics, as the moves are known to be valid
     #[must_use]
     pub fn sexy() -> Self {
         Self::from("R U R' U'").expect("this doesn't panic")
@@ -305,6 +636,9 @@ impl Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let m = match self {
             Self::FaceTurn(turn) => format!("{turn}"),
+            Self::Rotation(rotation) => format!("{rotation}"),
+            Self::Layer(layer) => format!("{layer}"),
+            Self::Slice(slice) => format!("{slice}"),
         };
         write!(f, "{m}")
     }
@@ -337,6 +671,54 @@ impl Display for FaceTurn {
     }
 }
 
+impl Display for Rotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let m = match self {
+            Self::X(1) => "x",
+            Self::X(2) => "x2",
+            Self::X(3) => "x'",
+            Self::Y(1) => "y",
+            Self::Y(2) => "y2",
+            Self::Y(3) => "y'",
+            Self::Z(1) => "z",
+            Self::Z(2) => "z2",
+            Self::Z(3) => "z'",
+            m => panic!("Unknown rotation: {m:?}"),
+        };
+        write!(f, "{m}")
+    }
+}
+
+impl Display for SliceTurn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let m = match self {
+            Self::M(1) => "M",
+            Self::M(2) => "M2",
+            Self::M(3) => "M'",
+            Self::E(1) => "E",
+            Self::E(2) => "E2",
+            Self::E(3) => "E'",
+            Self::S(1) => "S",
+            Self::S(2) => "S2",
+            Self::S(3) => "S'",
+            m => panic!("Unknown slice turn: {m:?}"),
+        };
+        write!(f, "{m}")
+    }
+}
+
+impl Display for LayerTurn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let turn = self.turn.to_string();
+        let (face, amount) = turn.split_at(1);
+        match (self.depth, self.wide) {
+            (2, true) => write!(f, "{face}w{amount}"),
+            (depth, true) => write!(f, "{depth}{face}w{amount}"),
+            (depth, false) => write!(f, "{depth}{face}{amount}"),
+        }
+    }
+}
+
 impl Add<Self> for FaceTurn {
     type Output = Algorithm;
 
@@ -377,18 +759,101 @@ impl Add<Self> for FaceTurn {
     }
 }
 
+impl Add<Self> for Rotation {
+    type Output = Algorithm;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let new_moves = match (self, rhs) {
+            (Self::X(a), Self::X(b)) => match (a + b) % 4 {
+                0 => Vec::new(),
+                t => vec![Self::X(t)],
+            },
+            (Self::Y(a), Self::Y(b)) => match (a + b) % 4 {
+                0 => Vec::new(),
+                t => vec![Self::Y(t)],
+            },
+            (Self::Z(a), Self::Z(b)) => match (a + b) % 4 {
+                0 => Vec::new(),
+                t => vec![Self::Z(t)],
+            },
+            (left, right) => vec![left, right],
+        };
+        new_moves.into()
+    }
+}
+
+impl Add<Self> for LayerTurn {
+    type Output = Algorithm;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if (self.depth, self.wide) != (rhs.depth, rhs.wide) {
+            return vec![self, rhs].into();
+        }
+        match (self.turn + rhs.turn).moves[..] {
+            [] => Algorithm::new(),
+            [Move::FaceTurn(turn)] => vec![Self { turn, ..self }].into(),
+            _ => vec![self, rhs].into(),
+        }
+    }
+}
+
+impl Add<Self> for SliceTurn {
+    type Output = Algorithm;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let new_moves = match (self, rhs) {
+            (Self::M(a), Self::M(b)) => match (a + b) % 4 {
+                0 => Vec::new(),
+                t => vec![Self::M(t)],
+            },
+            (Self::E(a), Self::E(b)) => match (a + b) % 4 {
+                0 => Vec::new(),
+                t => vec![Self::E(t)],
+            },
+            (Self::S(a), Self::S(b)) => match (a + b) % 4 {
+                0 => Vec::new(),
+                t => vec![Self::S(t)],
+            },
+            (left, right) => vec![left, right],
+        };
+        new_moves.into()
+    }
+}
+
 impl From<FaceTurn> for Move {
     fn from(value: FaceTurn) -> Self {
         Self::FaceTurn(value)
     }
 }
 
+impl From<Rotation> for Move {
+    fn from(value: Rotation) -> Self {
+        Self::Rotation(value)
+    }
+}
+
+impl From<LayerTurn> for Move {
+    fn from(value: LayerTurn) -> Self {
+        Self::Layer(value)
+    }
+}
+
+impl From<SliceTurn> for Move {
+    fn from(value: SliceTurn) -> Self {
+        Self::Slice(value)
+    }
+}
+
 impl Add<Self> for Move {
     type Output = Algorithm;
 
     fn add(self, rhs: Self) -> Self::Output {
         match (self, rhs) {
             (Self::FaceTurn(a), Self::FaceTurn(b)) => a + b,
+            (Self::Rotation(a), Self::Rotation(b)) => a + b,
+            (Self::Layer(a), Self::Layer(b)) => a + b,
+            (Self::Slice(a), Self::Slice(b)) => a + b,
+            (left, right) => vec![left, right].into(),
         }
     }
 }
@@ -403,15 +868,28 @@ impl Add<&Self> for Algorithm {
     }
 }
 
+impl Mul<u32> for Algorithm {
+    type Output = Self;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self {
+            moves: self.moves.repeat(rhs as usize),
+        }
+    }
+}
+
 macro_rules! move_parser {
     ($fn_name: ident,   $dir: ident, $d: expr ) => {
-        fn $fn_name(input: &str) -> IResult<&str, FaceTurn> {
+        move_parser!($fn_name, FaceTurn, $dir, $d);
+    };
+    ($fn_name: ident, $kind: ident, $dir: ident, $d: expr ) => {
+        fn $fn_name(input: &str) -> IResult<&str, $kind> {
             let (input, t) = alt((
                 map(tag(format!("{}2", $d).as_str()), |_| 2),
                 map(tag(format!("{}'", $d).as_str()), |_| 3),
                 map(tag(format!("{}", $d).as_str()), |_| 1),
             ))(input)?;
-            Ok((input, FaceTurn::$dir(t)))
+            Ok((input, $kind::$dir(t)))
         }
     };
 }
@@ -422,6 +900,71 @@ move_parser!(f_moves, F, "F");
 move_parser!(b_moves, B, "B");
 move_parser!(l_moves, L, "L");
 move_parser!(r_moves, R, "R");
+move_parser!(x_rotations, Rotation, X, "x");
+move_parser!(y_rotations, Rotation, Y, "y");
+move_parser!(z_rotations, Rotation, Z, "z");
+move_parser!(uw_moves, U, "Uw");
+move_parser!(dw_moves, D, "Dw");
+move_parser!(fw_moves, F, "Fw");
+move_parser!(bw_moves, B, "Bw");
+move_parser!(lw_moves, L, "Lw");
+move_parser!(rw_moves, R, "Rw");
+move_parser!(u_wide, U, "u");
+move_parser!(d_wide, D, "d");
+move_parser!(f_wide, F, "f");
+move_parser!(b_wide, B, "b");
+move_parser!(l_wide, L, "l");
+move_parser!(r_wide, R, "r");
+move_parser!(m_slices, SliceTurn, M, "M");
+move_parser!(e_slices, SliceTurn, E, "E");
+move_parser!(s_slices, SliceTurn, S, "S");
+
+/// Only accepts what `parser` reads when a space or the end of the input comes after it, so that
+/// the start of an unknown word isn't read as a lowercase or slice turn
+fn whole<'a, O>(
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    terminated(parser, peek(alt((space1, eof))))
+}
+
+fn depth(input: &str) -> IResult<&str, u8> {
+    verify(map_res(digit1, str::parse), |&depth| depth > 0)(input)
+}
+
+fn layer_turns(input: &str) -> IResult<&str, LayerTurn> {
+    alt((
+        map(
+            pair(
+                opt(depth),
+                alt((uw_moves, dw_moves, fw_moves, bw_moves, lw_moves, rw_moves)),
+            ),
+            |(depth, turn)| LayerTurn {
+                turn,
+                depth: depth.unwrap_or(2),
+                wide: true,
+            },
+        ),
+        map(
+            pair(
+                depth,
+                alt((u_moves, d_moves, f_moves, b_moves, l_moves, r_moves)),
+            ),
+            |(depth, turn)| LayerTurn {
+                turn,
+                depth,
+                wide: false,
+            },
+        ),
+        map(
+            whole(alt((u_wide, d_wide, f_wide, b_wide, l_wide, r_wide))),
+            |turn| LayerTurn {
+                turn,
+                depth: 2,
+                wide: true,
+            },
+        ),
+    ))(input)
+}
 
 impl From<nom::Err<nom::error::Error<&str>>> for MoveParseError {
     fn from(value: nom::Err<nom::error::Error<&str>>) -> Self {
@@ -441,6 +984,7 @@ where
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_clone)]
 mod simplification_tests {
     use super::*;
     use pretty_assertions::{assert_eq, assert_ne};
@@ -560,6 +1104,15 @@ mod simplification_tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn matching_layer_turns_simplify() {
+        let moves = Algorithm::from("Rw Rw 2R 2R' 3Rw").unwrap();
+        let actual = moves.simplify();
+        let expected = Algorithm::from("Rw2 3Rw").unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     #[allow(non_snake_case)]
     fn U2_D2_U2_simplifies_the_two_outside_U2s() {
@@ -642,6 +1195,105 @@ mod parsing_tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn rotations() {
+        let s = "x y' z2";
+        let actual = Algorithm::from(s).unwrap();
+        let expected = vec![Rotation::X(1), Rotation::Y(3), Rotation::Z(2)].into();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn layer_turns() {
+        let s = "3Rw' 2R2 Uw";
+        let actual = Algorithm::from(s).unwrap();
+        let expected = vec![
+            LayerTurn {
+                turn: FaceTurn::R(3),
+                depth: 3,
+                wide: true,
+            },
+            LayerTurn {
+                turn: FaceTurn::R(2),
+                depth: 2,
+                wide: false,
+            },
+            LayerTurn {
+                turn: FaceTurn::U(1),
+                depth: 2,
+                wide: true,
+            },
+        ]
+        .into();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn layer_turns_display_as_they_are_written() {
+        let s = "Rw 3Fw2 2D' 4Lw'";
+        let actual = format!("{}", Algorithm::from(s).unwrap());
+
+        assert_str_eq!(actual, s);
+    }
+
+    #[test]
+    fn slice_turns_display_as_they_are_written() {
+        let s = "M' E2 S M2";
+        let actual = format!("{}", Algorithm::from(s).unwrap());
+
+        assert_str_eq!(actual, s);
+    }
+
+    #[test]
+    fn lowercase_turns_are_wide() {
+        assert_eq!(
+            Algorithm::from("r U' l2").unwrap(),
+            Algorithm::from("Rw U' Lw2").unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_words_are_reported_whole() {
+        assert_eq!(
+            Algorithm::from("R Mop U"),
+            Err(MoveParseError::UnknownSymbol(" Mop U".to_string()))
+        );
+        assert_eq!(
+            Algorithm::from("r2x"),
+            Err(MoveParseError::UnknownSymbol("r2x".to_string()))
+        );
+        assert_eq!(
+            Algorithm::from("M' r"),
+            Ok(vec![
+                Move::Slice(SliceTurn::M(3)),
+                Move::Layer(LayerTurn {
+                    turn: FaceTurn::R(1),
+                    depth: 2,
+                    wide: true,
+                })
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn matching_slice_turns_simplify() {
+        let moves = Algorithm::from("M M' E S2 S2 M' M'").unwrap();
+
+        assert_eq!(moves.simplify(), Algorithm::from("E M2").unwrap());
+        assert_eq!(
+            moves.inverse(),
+            Algorithm::from("M M S2 S2 E' M M'").unwrap()
+        );
+    }
+
+    #[test]
+    fn errors_on_layer_zero() {
+        assert!(Algorithm::from("0R").is_err());
+    }
+
     #[test]
     fn errors_on_unknown_input() {
         let s = "R U foobar R' U'";
@@ -738,6 +1390,38 @@ mod order_tests {
     }
 }
 
+#[cfg(test)]
+mod power_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn negative_powers_repeat_the_inverse() {
+        let alg = Algorithm::from("R U' F2").unwrap();
+
+        assert_eq!(alg.pow(-2), Algorithm::from("F2 U R' F2 U R'").unwrap());
+    }
+
+    #[test]
+    fn multiplying_repeats() {
+        let alg = Algorithm::from("R U").unwrap();
+
+        assert_eq!(alg.clone() * 3, alg.pow(3));
+        assert_eq!(Algorithm::new() * 5, Algorithm::new());
+    }
+
+    #[test]
+    fn the_order_power_solves() {
+        let sexy = Algorithm::sexy();
+
+        assert_eq!(
+            Cube::new().apply(sexy.pow(sexy.order().try_into().unwrap())),
+            Cube::new()
+        );
+    }
+}
+
 #[cfg(test)]
 mod solves_tests {
     use super::*;
@@ -779,3 +1463,38 @@ mod tests {
         assert_str_eq!(actual, moves);
     }
 }
+
+#[cfg(test)]
+mod mirror_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn mirrored_algs_mirror_the_cube() {
+        let alg = Algorithm::from("R U2 F' x Rw 2D' L B2 z").unwrap();
+        for mirror in [Mirror::M, Mirror::S, Mirror::E] {
+            let mirrored = alg.mirror(mirror);
+
+            assert_eq!(alg.order(), mirrored.order());
+            assert_eq!(mirrored.mirror(mirror), alg);
+        }
+    }
+
+    #[test]
+    fn mirror_across_s() {
+        let actual = Algorithm::from("F R y z").unwrap().mirror(Mirror::S);
+        let expected = Algorithm::from("B' R' y' z").unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wide_turns_count_once() {
+        let alg = Algorithm::from("Rw2 3Uw' x2").unwrap();
+
+        assert_eq!(alg.length(Metric::Htm), 2);
+        assert_eq!(alg.length(Metric::Qtm), 3);
+        assert_eq!(alg.length(Metric::Etm), 3);
+    }
+}
@@ -0,0 +1,321 @@
+//! A model of cubes of any size, like the 4x4 through 7x7
+use std::fmt::Display;
+
+use crate::{
+    color_scheme::ColorScheme,
+    cube::{Face, Side},
+    moves::{Algorithm, LayerTurn, Move, Rotation},
+    stickers::{Sticker, Stickers},
+    terminal::{self, Style},
+};
+
+/// The state of a cube with any number of layers, which [`Algorithm`]s can be applied to
+///
+/// Face turns only turn the outer layer, and rotations turn every layer
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{moves::Algorithm, nxn::NxNCube};
+///
+/// let cube = NxNCube::new(5).apply(Algorithm::from("3Rw U 2L'").unwrap());
+///
+/// assert!(!cube.is_solved());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct NxNCube {
+    size: usize,
+    /// Every sticker of every face, in the same order as the faces and rows of the [`Display`] net
+    stickers: Vec<Side>,
+}
+
+/// Turns `v` clockwise by a quarter, looking at the cube from the direction `axis` points
+fn quarter_turn(v: [i32; 3], axis: [i32; 3]) -> [i32; 3] {
+    let dot = v[0] * axis[0] + v[1] * axis[1] + v[2] * axis[2];
+    let cross = [
+        axis[1] * v[2] - axis[2] * v[1],
+        axis[2] * v[0] - axis[0] * v[2],
+        axis[0] * v[1] - axis[1] * v[0],
+    ];
+    [0, 1, 2].map(|i| axis[i] * dot - cross[i])
+}
+
+impl NxNCube {
+    /// Creates a solved cube with `size` layers, with yellow on top and red in front
+    ///
+    /// # Panics
+    ///
+    /// This panics if `size` is 0
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self::new_with_scheme(size, ColorScheme::default())
+    }
+
+    /// Creates a solved cube with `size` layers, colored and held the way `scheme` says
+    ///
+    /// # Panics
+    ///
+    /// This panics if `size` is 0
+    #[must_use]
+    pub fn new_with_scheme(size: usize, scheme: ColorScheme) -> Self {
+        assert!(size > 0, "a cube needs at least one layer");
+        Self {
+            size,
+            stickers: Face::ALL
+                .into_iter()
+                .flat_map(|face| std::iter::repeat_n(scheme.color(face), size * size))
+                .collect(),
+        }
+    }
+
+    /// The number of layers of the cube
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Performs every move of an [`Algorithm`], in order, on a copy of this cube
+    ///
+    /// Wide turns deeper than the cube turn the whole cube, and turns of single layers deeper
+    /// than the cube do nothing. Slice turns like M turn the middle layer, which on even cubes
+    /// is the inner layer nearer L, D, or F
+    #[must_use]
+    pub fn apply(&self, moves: Algorithm) -> Self {
+        moves
+            .into_iter()
+            .fold(self.clone(), |cube, m| cube.apply_move(m))
+    }
+
+    fn apply_move(&self, m: Move) -> Self {
+        let size = u8::try_from(self.size).unwrap_or(u8::MAX);
+        let layer = match m {
+            Move::FaceTurn(turn) => LayerTurn {
+                turn,
+                depth: 1,
+                wide: false,
+            },
+            Move::Rotation(rotation) => {
+                let (Rotation::X(n) | Rotation::Y(n) | Rotation::Z(n)) = rotation;
+                let face = match rotation {
+                    Rotation::X(_) => Face::R,
+                    Rotation::Y(_) => Face::U,
+                    Rotation::Z(_) => Face::F,
+                };
+                LayerTurn {
+                    turn: face.turn(n),
+                    depth: size,
+                    wide: true,
+                }
+            }
+            Move::Layer(layer) => layer,
+            Move::Slice(slice) => LayerTurn {
+                depth: size.div_ceil(2),
+                ..slice.layer()
+            },
+        };
+        let (face, amount) = layer.turn.face();
+        (0..amount).fold(self.clone(), |cube, _| {
+            cube.quarter_turn(face, usize::from(layer.depth), layer.wide)
+        })
+    }
+
+    /// Where `sticker` is kept in `stickers`
+    const fn index(&self, sticker: Sticker) -> usize {
+        (sticker.face as usize * self.size + sticker.row) * self.size + sticker.col
+    }
+
+    fn quarter_turn(&self, face: Face, depth: usize, wide: bool) -> Self {
+        let axis = face.normal();
+        let max = i32::try_from(self.size - 1).expect("cubes are not that big");
+        let mut cube = self.clone();
+        for from in Stickers::stickers(self) {
+            let location = from.location(self.size);
+            let along = location[0] * axis[0] + location[1] * axis[1] + location[2] * axis[2];
+            let layer = usize::try_from((max - along) / 2 + 1).expect("layers start at 1");
+            if (wide && layer <= depth) || (!wide && layer == depth) {
+                let face = Face::from_normal(quarter_turn(from.face.normal(), axis));
+                let to = Sticker::at(face, quarter_turn(location, axis), self.size);
+                cube.stickers[self.index(to)] = self.stickers[self.index(from)];
+            }
+        }
+        cube
+    }
+
+    /// Determines if every face is a single color, no matter which way the cube is held
+    ///
+    /// Only stickers are tracked, so centers that are turned in place still count as solved
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rubiks_moves::{moves::Algorithm, nxn::NxNCube};
+    ///
+    /// let cube = NxNCube::new(4).apply(Algorithm::from("Rw Lw'").unwrap());
+    ///
+    /// assert!(cube.is_solved());
+    /// assert_ne!(cube, NxNCube::new(4));
+    /// ```
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.stickers
+            .chunks(self.size * self.size)
+            .all(|face| face.iter().all(|&sticker| sticker == face[0]))
+    }
+}
+
+impl Stickers for NxNCube {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn color(&self, sticker: Sticker) -> Side {
+        self.stickers[self.index(sticker)]
+    }
+}
+
+impl Display for NxNCube {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let style = if f.alternate() {
+            Style::Ansi
+        } else {
+            Style::Emoji
+        };
+        write!(f, "{}", terminal::net(self, style))
+    }
+}
+
+#[cfg(test)]
+mod nxn_tests {
+    use super::*;
+    use crate::cube::Cube;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn matches_the_3x3() {
+        let moves = "R U2 F' x 2R Dw' L 3B2 z' Bw y2 2U";
+        let actual = format!("{}", NxNCube::new(3).apply(Algorithm::from(moves).unwrap()));
+        let expected = format!("{}", Cube::new().apply(Algorithm::from(moves).unwrap()));
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn basic_4x4_display() {
+        let cube = NxNCube::new(4);
+        let actual = format!("{cube}");
+        let expected = "⬛⬛⬛⬛🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛
+🟦🟦🟦🟦🟥🟥🟥🟥🟩🟩🟩🟩🟧🟧🟧🟧
+🟦🟦🟦🟦🟥🟥🟥🟥🟩🟩🟩🟩🟧🟧🟧🟧
+🟦🟦🟦🟦🟥🟥🟥🟥🟩🟩🟩🟩🟧🟧🟧🟧
+🟦🟦🟦🟦🟥🟥🟥🟥🟩🟩🟩🟩🟧🟧🟧🟧
+⬛⬛⬛⬛⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wide_r_on_4x4() {
+        let cube = NxNCube::new(4).apply(Algorithm::from("Rw").unwrap());
+        let actual = format!("{cube}");
+        let expected = "⬛⬛⬛⬛🟨🟨🟥🟥⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛🟨🟨🟥🟥⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛🟨🟨🟥🟥⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛🟨🟨🟥🟥⬛⬛⬛⬛⬛⬛⬛⬛
+🟦🟦🟦🟦🟥🟥⬜⬜🟩🟩🟩🟩🟨🟨🟧🟧
+🟦🟦🟦🟦🟥🟥⬜⬜🟩🟩🟩🟩🟨🟨🟧🟧
+🟦🟦🟦🟦🟥🟥⬜⬜🟩🟩🟩🟩🟨🟨🟧🟧
+🟦🟦🟦🟦🟥🟥⬜⬜🟩🟩🟩🟩🟨🟨🟧🟧
+⬛⬛⬛⬛⬜⬜🟧🟧⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬜⬜🟧🟧⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬜⬜🟧🟧⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬜⬜🟧🟧⬛⬛⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn slices_turn_the_middle_layer() {
+        assert_eq!(
+            NxNCube::new(5).apply(Algorithm::from("M E'").unwrap()),
+            NxNCube::new(5).apply(Algorithm::from("3L 3D'").unwrap())
+        );
+        assert_eq!(
+            NxNCube::new(4).apply(Algorithm::from("S2").unwrap()),
+            NxNCube::new(4).apply(Algorithm::from("2F2").unwrap())
+        );
+    }
+
+    #[test]
+    fn inner_u_layer_on_5x5() {
+        let cube = NxNCube::new(5).apply(Algorithm::from("3U").unwrap());
+        let actual = format!("{cube}");
+        let expected = "⬛⬛⬛⬛⬛🟨🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛🟨🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛🟨🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛🟨🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛🟨🟨🟨🟨🟨⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+🟦🟦🟦🟦🟦🟥🟥🟥🟥🟥🟩🟩🟩🟩🟩🟧🟧🟧🟧🟧
+🟦🟦🟦🟦🟦🟥🟥🟥🟥🟥🟩🟩🟩🟩🟩🟧🟧🟧🟧🟧
+🟥🟥🟥🟥🟥🟩🟩🟩🟩🟩🟧🟧🟧🟧🟧🟦🟦🟦🟦🟦
+🟦🟦🟦🟦🟦🟥🟥🟥🟥🟥🟩🟩🟩🟩🟩🟧🟧🟧🟧🟧
+🟦🟦🟦🟦🟦🟥🟥🟥🟥🟥🟩🟩🟩🟩🟩🟧🟧🟧🟧🟧
+⬛⬛⬛⬛⬛⬜⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛⬜⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛⬜⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛⬜⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛
+⬛⬛⬛⬛⬛⬜⬜⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛";
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn every_move_has_the_right_order() {
+        for size in 4..=7 {
+            let cube = NxNCube::new(size);
+            for moves in ["3Rw", "2F'", "Uw2", "x", "L"] {
+                let alg = Algorithm::from(moves).unwrap();
+                let turned = (0..4).fold(cube.clone(), |c, _| c.apply(alg.clone()));
+
+                assert_eq!(turned, cube, "{moves} on {size}x{size}");
+            }
+        }
+    }
+
+    #[test]
+    fn scrambles_are_undone_by_their_inverse() {
+        let scramble = Algorithm::from("3Rw 2U' Fw2 4L x 3D 6Bw' R z'").unwrap();
+        let cube = NxNCube::new(7).apply(scramble.clone());
+
+        assert!(!cube.is_solved());
+        assert_eq!(cube.apply(scramble.inverse()), NxNCube::new(7));
+    }
+
+    #[test]
+    fn schemes_match_the_3x3() {
+        let scheme = ColorScheme::japanese()
+            .held(Side::Red, Side::Yellow)
+            .unwrap();
+        let moves = Algorithm::from("R U F' 2R").unwrap();
+        let actual = format!(
+            "{}",
+            NxNCube::new_with_scheme(3, scheme).apply(moves.clone())
+        );
+        let expected = format!("{}", Cube::new_with_scheme(scheme).apply(moves));
+
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rotations_are_solved() {
+        let cube = NxNCube::new(6).apply(Algorithm::from("x y'").unwrap());
+
+        assert!(cube.is_solved());
+    }
+}
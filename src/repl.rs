@@ -0,0 +1,320 @@
+//! An interactive shell for trying out algorithms on a cube
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+use rubiks_moves::{
+    cube::Cube,
+    moves::{Algorithm, MoveParseError},
+    solvers::thistlethwaite,
+    terminal::{self, Style},
+};
+use thiserror::Error;
+
+const HELP: &str =
+    "Type moves like R U R' U' to turn the cube, or use names that were saved before.
+A saved name can be followed by ' or 2 to undo it or do it twice.
+
+    name = R U R' U'   Saves moves under a name, without turning the cube
+    :undo              Takes back the last line of moves
+    :redo              Does the last line that was taken back again
+    :reset             Goes back to a solved cube
+    :inverse [moves]   Prints the inverse of the moves, or of every move so far
+    :order [moves]     Prints the order of the moves, or of every move so far
+    :solve             Prints moves that solve the cube, found with Thistlethwaite's method
+    :vars              Lists the saved names
+    :help              Prints this message
+    :quit              Leaves the shell";
+
+/// Occurs when a line typed into the shell can't be used
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReplError {
+    #[error("unknown command :{0}")]
+    UnknownCommand(String),
+    #[error("there is nothing to undo")]
+    NothingToUndo,
+    #[error("there is nothing to redo")]
+    NothingToRedo,
+    #[error("{0} can't be used as a name")]
+    BadName(String),
+    #[error(transparent)]
+    Parse(#[from] MoveParseError),
+}
+
+/// What the shell does after a line is typed
+#[derive(Debug, PartialEq, Eq)]
+pub enum Reply {
+    Print(String),
+    Quit,
+}
+
+/// The state of a shell, with every line of moves that has been done to the cube
+#[derive(Debug, Default)]
+pub struct Repl {
+    style: Style,
+    done: Vec<Algorithm>,
+    undone: Vec<Algorithm>,
+    variables: BTreeMap<String, Algorithm>,
+}
+
+impl Repl {
+    pub fn new(style: Style) -> Self {
+        Self {
+            style,
+            ..Self::default()
+        }
+    }
+
+    /// Every move that has been done to the cube so far
+    fn moves(&self) -> Algorithm {
+        self.done
+            .iter()
+            .fold(Algorithm::new(), |all, moves| all + moves)
+    }
+
+    /// Moves that solve the cube, with the rotation that puts its centers back at the end
+    ///
+    /// Thistlethwaite's method solves the pieces around the centers, wherever slices and
+    /// rotations have left them
+    fn solution(&self) -> Algorithm {
+        let cube = Cube::new().apply(self.moves());
+        let turns = thistlethwaite::solve(&cube)
+            .iter()
+            .fold(Algorithm::new(), |all, moves| all + moves);
+        let turned = cube.apply(turns.clone());
+        let rotation = ["", "x", "x2", "x'", "z", "z'"]
+            .into_iter()
+            .flat_map(|first| ["", "y", "y2", "y'"].map(|then| format!("{first} {then}")))
+            .map(|rotation| Algorithm::from(rotation.trim()).expect("the rotations are valid"))
+            .find(|rotation| turned.apply(rotation.clone()) == Cube::new())
+            .expect("solved pieces are solved in some orientation");
+        (turns + &rotation).simplify()
+    }
+
+    fn net(&self) -> String {
+        terminal::net(&Cube::new().apply(self.moves()), self.style)
+    }
+
+    /// Reads moves, where saved names can be used in place of the moves they hold
+    fn expand(&self, input: &str) -> Result<Algorithm, ReplError> {
+        input
+            .split_whitespace()
+            .try_fold(Algorithm::new(), |all, word| {
+                let variable = |name: &str| self.variables.get(name).cloned();
+                let moves = if let Some(moves) = variable(word) {
+                    moves
+                } else if let Some(moves) = word.strip_suffix('\'').and_then(variable) {
+                    moves.inverse()
+                } else if let Some(moves) = word.strip_suffix('2').and_then(variable) {
+                    moves.clone() + &moves
+                } else {
+                    Algorithm::from(word)?
+                };
+                Ok(all + &moves)
+            })
+    }
+
+    /// The moves given after a command, or every move so far when there are none
+    fn argument(&self, input: &str) -> Result<Algorithm, ReplError> {
+        if input.trim().is_empty() {
+            Ok(self.moves())
+        } else {
+            self.expand(input)
+        }
+    }
+
+    /// Reacts to one line typed into the shell
+    ///
+    /// # Errors
+    ///
+    /// This errors when the line isn't moves, a command or a definition that the shell knows
+    pub fn handle(&mut self, line: &str) -> Result<Reply, ReplError> {
+        let line = line.trim();
+        if let Some(command) = line.strip_prefix(':') {
+            let (name, rest) = command.split_once(' ').unwrap_or((command, ""));
+            return self.command(name, rest);
+        }
+        if let Some((name, moves)) = line.split_once('=') {
+            let name = name.trim();
+            let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && Algorithm::from(name).is_err();
+            if !valid {
+                return Err(ReplError::BadName(name.to_string()));
+            }
+            let moves = self.expand(moves)?;
+            let reply = format!("{name} = {moves}");
+            self.variables.insert(name.to_string(), moves);
+            return Ok(Reply::Print(reply));
+        }
+        if line.is_empty() {
+            return Ok(Reply::Print(self.net()));
+        }
+        let moves = self.expand(line)?;
+        self.done.push(moves);
+        self.undone.clear();
+        Ok(Reply::Print(self.net()))
+    }
+
+    fn command(&mut self, name: &str, rest: &str) -> Result<Reply, ReplError> {
+        let text = match name {
+            "undo" => {
+                let moves = self.done.pop().ok_or(ReplError::NothingToUndo)?;
+                self.undone.push(moves);
+                self.net()
+            }
+            "redo" => {
+                let moves = self.undone.pop().ok_or(ReplError::NothingToRedo)?;
+                self.done.push(moves);
+                self.net()
+            }
+            "reset" => {
+                self.done.clear();
+                self.undone.clear();
+                self.net()
+            }
+            "inverse" => self.argument(rest)?.inverse().to_string(),
+            "order" => self.argument(rest)?.order().to_string(),
+            "solve" => self.solution().to_string(),
+            "vars" => self
+                .variables
+                .iter()
+                .map(|(name, moves)| format!("{name} = {moves}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "help" => HELP.to_string(),
+            "quit" | "exit" => return Ok(Reply::Quit),
+            _ => return Err(ReplError::UnknownCommand(name.to_string())),
+        };
+        Ok(Reply::Print(text))
+    }
+}
+
+/// Runs the shell until `input` ends or `:quit` is typed
+///
+/// # Errors
+///
+/// This errors when `input` can't be read or `output` can't be written to
+pub fn run(style: Style, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut repl = Repl::new(style);
+    writeln!(output, "Type :help to see what can be done")?;
+    write!(output, "> ")?;
+    output.flush()?;
+    for line in input.lines() {
+        match repl.handle(&line?) {
+            Ok(Reply::Print(text)) => writeln!(output, "{text}")?,
+            Ok(Reply::Quit) => return Ok(()),
+            Err(error) => writeln!(output, "error: {error}")?,
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+    writeln!(output)
+}
+
+#[cfg(test)]
+mod repl_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn print(repl: &mut Repl, line: &str) -> String {
+        match repl.handle(line).unwrap() {
+            Reply::Print(text) => text,
+            Reply::Quit => panic!("the shell quit"),
+        }
+    }
+
+    #[test]
+    fn moves_update_the_net() {
+        let mut repl = Repl::new(Style::Letters);
+        let actual = print(&mut repl, "R");
+
+        assert_str_eq!(
+            actual,
+            "   YYR
+   YYR
+   YYR
+BBBRRWGGGYOO
+BBBRRWGGGYOO
+BBBRRWGGGYOO
+   WWO
+   WWO
+   WWO"
+        );
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut repl = Repl::new(Style::Letters);
+        let solved = print(&mut repl, "");
+        let turned = print(&mut repl, "R U");
+
+        assert_str_eq!(print(&mut repl, ":undo"), solved);
+        assert_str_eq!(print(&mut repl, ":redo"), turned);
+        assert_eq!(repl.handle(":redo"), Err(ReplError::NothingToRedo));
+        print(&mut repl, ":reset");
+        assert_eq!(repl.handle(":undo"), Err(ReplError::NothingToUndo));
+    }
+
+    #[test]
+    fn variables() {
+        let mut repl = Repl::new(Style::Letters);
+
+        assert_str_eq!(print(&mut repl, "sexy = R U R' U'"), "sexy = R U R' U'");
+        assert_str_eq!(print(&mut repl, ":inverse sexy F"), "F' U R U' R'");
+        assert_str_eq!(print(&mut repl, ":inverse sexy'"), "R U R' U'");
+        assert_str_eq!(print(&mut repl, ":order sexy2"), "3");
+        assert_eq!(
+            repl.handle("R2 = U"),
+            Err(ReplError::BadName("R2".to_string()))
+        );
+    }
+
+    #[test]
+    fn commands_use_every_move_so_far() {
+        let mut repl = Repl::new(Style::Letters);
+        print(&mut repl, "R U");
+        print(&mut repl, "U R'");
+
+        assert_str_eq!(print(&mut repl, ":inverse"), "R U' U' R'");
+        assert_str_eq!(print(&mut repl, ":order"), "2");
+    }
+
+    #[test]
+    fn solve_finds_a_solution() {
+        let mut repl = Repl::new(Style::Letters);
+        print(&mut repl, "R U F' D2");
+        let solution = print(&mut repl, ":solve");
+
+        assert!(Algorithm::from(&solution)
+            .unwrap()
+            .solves(&Algorithm::from("R U F' D2").unwrap()));
+    }
+
+    #[test]
+    fn solve_puts_the_centers_back() {
+        for moves in ["x R U", "M U M'", "R y F' E2 S"] {
+            let mut repl = Repl::new(Style::Letters);
+            print(&mut repl, moves);
+            let solution = print(&mut repl, ":solve");
+
+            assert!(
+                Algorithm::from(&solution)
+                    .unwrap()
+                    .solves(&Algorithm::from(moves).unwrap()),
+                "{solution} after {moves}"
+            );
+        }
+    }
+
+    #[test]
+    fn run_reads_until_quit() {
+        let mut output = Vec::new();
+        run(Style::Letters, &b":order R\n:quit\nR\n"[..], &mut output).unwrap();
+        let actual = String::from_utf8(output).unwrap();
+
+        assert_str_eq!(actual, "Type :help to see what can be done\n> 4\n> ");
+    }
+}
@@ -0,0 +1,125 @@
+//! [`Serialize`] and [`Deserialize`] for the types people store, behind the `serde` feature
+//!
+//! Moves and algorithms are written in their usual notation, and cubes as their
+//! [facelets](crate::facelets), so they read the same in JSON as they do anywhere else
+use std::fmt::Display;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    cube::Cube,
+    moves::{Algorithm, FaceTurn, LayerTurn, Move, Rotation, SliceTurn},
+};
+
+fn parse_move<E: Error>(notation: &str) -> Result<Move, E> {
+    let alg = Algorithm::from(notation).map_err(E::custom)?;
+    match alg.moves.as_slice() {
+        &[single] => Ok(single),
+        _ => Err(E::custom(format!("{notation} is not a single move"))),
+    }
+}
+
+macro_rules! as_notation {
+    ($kind: ty, $parse: expr) => {
+        impl Serialize for $kind {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $kind {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let notation = String::deserialize(deserializer)?;
+                $parse(notation.as_str())
+            }
+        }
+    };
+}
+
+fn wrong_kind<E: Error>(found: impl Display, expected: &str) -> E {
+    E::custom(format!("{found} is not {expected}"))
+}
+
+as_notation!(Algorithm, |notation| Algorithm::from(notation)
+    .map_err(D::Error::custom));
+as_notation!(Move, parse_move);
+as_notation!(FaceTurn, |notation| match parse_move(notation)? {
+    Move::FaceTurn(turn) => Ok(turn),
+    other => Err(wrong_kind(other, "a face turn")),
+});
+as_notation!(Rotation, |notation| match parse_move(notation)? {
+    Move::Rotation(rotation) => Ok(rotation),
+    other => Err(wrong_kind(other, "a rotation")),
+});
+as_notation!(LayerTurn, |notation| match parse_move(notation)? {
+    Move::Layer(turn) => Ok(turn),
+    other => Err(wrong_kind(other, "a layer turn")),
+});
+as_notation!(SliceTurn, |notation| match parse_move(notation)? {
+    Move::Slice(turn) => Ok(turn),
+    other => Err(wrong_kind(other, "a slice turn")),
+});
+
+impl Serialize for Cube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.facelets())
+    }
+}
+
+impl<'de> Deserialize<'de> for Cube {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let facelets = String::deserialize(deserializer)?;
+        Self::from_facelets(&facelets).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod serialization_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn algorithms_are_notation() {
+        let alg = Algorithm::from("R U2 x' Rw 3Uw'").unwrap();
+        let json = serde_json::to_string(&alg).unwrap();
+
+        assert_str_eq!(json, r#""R U2 x' Rw 3Uw'""#);
+        assert_eq!(serde_json::from_str::<Algorithm>(&json).unwrap(), alg);
+    }
+
+    #[test]
+    fn algorithms_are_validated() {
+        assert!(serde_json::from_str::<Algorithm>(r#""R Q""#).is_err());
+        assert!(serde_json::from_str::<Algorithm>("3").is_err());
+    }
+
+    #[test]
+    fn single_moves() {
+        assert_str_eq!(serde_json::to_string(&FaceTurn::F(3)).unwrap(), r#""F'""#);
+        assert_eq!(
+            serde_json::from_str::<Move>(r#""y2""#).unwrap(),
+            Move::Rotation(Rotation::Y(2))
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<FaceTurn>>(r#"["U", "D2"]"#).unwrap(),
+            vec![FaceTurn::U(1), FaceTurn::D(2)]
+        );
+        assert_eq!(
+            serde_json::from_str::<SliceTurn>(r#""M'""#).unwrap(),
+            SliceTurn::M(3)
+        );
+        assert!(serde_json::from_str::<FaceTurn>(r#""x""#).is_err());
+        assert!(serde_json::from_str::<Move>(r#""R U""#).is_err());
+    }
+
+    #[test]
+    fn cubes_are_facelets() {
+        let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+        let json = serde_json::to_string(&cube).unwrap();
+
+        assert_str_eq!(json, format!(r#""{}""#, cube.facelets()));
+        assert_eq!(serde_json::from_str::<Cube>(&json).unwrap(), cube);
+        assert!(serde_json::from_str::<Cube>(r#""YYY""#).is_err());
+    }
+}
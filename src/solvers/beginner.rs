@@ -0,0 +1,546 @@
+//! Solves the cube layer by layer, the way most people first learn to
+//!
+//! The white cross goes on the bottom, then the white corners and the middle layer edges are put
+//! in one at a time. The last layer is solved with a handful of algorithms: F R U R' U' F' for
+//! the yellow cross, Sune to match the yellow edges, U R U' L' U R' U' L to put the yellow
+//! corners in place, and R' D' R D to twist them
+//!
+//! Each [`Step`] says which [`Stage`] it belongs to and explains what it does, so the solution
+//! can be walked through one step at a time
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::beginner};
+//!
+//! let scramble = Algorithm::from("R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F").unwrap();
+//! let steps = beginner::solve(&Cube::new().apply(scramble.clone())).unwrap();
+//!
+//! for step in &steps {
+//!     println!("{}: {}", step.explanation, step.moves);
+//! }
+//! let solution = steps.iter().fold(Algorithm::new(), |all, step| all + &step.moves);
+//! assert!(solution.solves(&scramble));
+//! ```
+use crate::{
+    cube::{Cube, Face, Side, CORNER_FACES, EDGE_FACES},
+    moves::{Algorithm, Move, Rotation},
+};
+
+use super::{cross, rotation_to_bottom, unrotate};
+
+/// The parts of the beginner's method, in the order they are solved
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    /// The four white edges on the bottom, matching the centers beside them
+    Cross,
+    /// The four white corners, finishing the first layer
+    FirstLayerCorners,
+    /// The four edges between the top and bottom layers
+    MiddleLayer,
+    /// The four yellow edges facing up
+    YellowCross,
+    /// The yellow edges matching the centers beside them
+    YellowEdges,
+    /// The yellow corners in the right places, though maybe twisted
+    YellowCornerPositions,
+    /// The yellow corners twisted so yellow faces up, which solves the cube
+    YellowCornerOrientation,
+}
+
+/// Some moves that finish part of a [`Stage`]
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct Step {
+    pub stage: Stage,
+    /// What the moves do, in words
+    pub explanation: String,
+    pub moves: Algorithm,
+}
+
+/// The D corners, in the order they are inserted
+const FIRST_LAYER_CORNERS: [usize; 4] = [3, 4, 5, 2];
+
+/// The E slice edges, in the order they are inserted
+const MIDDLE_EDGES: [usize; 4] = [5, 6, 7, 4];
+
+/// Inserts the UF edge into the FR slot
+const RIGHT_INSERT: &str = "U R U' R' U' F' U F";
+
+/// Inserts the UF edge into the FL slot
+const LEFT_INSERT: &str = "U' L' U L U F U' F'";
+
+/// Flips the UF and UR edges, or UR and UL if they are in a line
+const EDGE_FLIP: &str = "F R U R' U' F'";
+
+const SUNE: &str = "R U R' U R U2 R'";
+
+/// Cycles every top corner except UFR
+const NIKLAS: &str = "U R U' L' U R' U' L";
+
+/// Twists the corner at UFR, and messes up the bottom until the corner is twisted right
+const CORNER_TWIST: &str = "R' D' R D";
+
+fn algorithm(moves: &str) -> Algorithm {
+    Algorithm::from(moves).expect("the beginner's algorithms are valid")
+}
+
+/// The ways to turn the top layer, starting with not turning it
+fn top_turns() -> [Algorithm; 4] {
+    ["", "U", "U2", "U'"].map(algorithm)
+}
+
+/// The same moves done from another side, after `quarters` y rotations, but without rotating
+fn from_side(moves: &Algorithm, quarters: u8) -> Algorithm {
+    if quarters == 0 {
+        return moves.clone();
+    }
+    moves
+        .moves
+        .iter()
+        .map(|&m| match m {
+            Move::FaceTurn(turn) => Move::FaceTurn(unrotate(turn, Rotation::Y(quarters))),
+            other => other,
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// `moves` done from each of the four sides
+fn from_every_side(moves: &Algorithm) -> Vec<Algorithm> {
+    (0..4).map(|quarters| from_side(moves, quarters)).collect()
+}
+
+/// Each of `moves` after each of the [`top_turns`], shortest first
+fn after_top_turns(moves: &[Algorithm]) -> Vec<Algorithm> {
+    let mut all: Vec<Algorithm> = top_turns()
+        .iter()
+        .flat_map(|turn| moves.iter().map(move |m| turn.clone() + m))
+        .collect();
+    all.sort_by_key(|m| m.moves.len());
+    all
+}
+
+/// Finds the fewest `steps`, done one after another, that reach `goal`, trying up to `depth` of
+/// them
+///
+/// Among equally few steps, the earliest ones in `steps` are preferred
+fn search(
+    cube: &Cube,
+    steps: &[Algorithm],
+    depth: usize,
+    goal: impl Fn(&Cube) -> bool,
+) -> Option<Algorithm> {
+    let mut level = vec![(Algorithm::new(), cube.clone())];
+    for done in 0..=depth {
+        if let Some((moves, _)) = level.iter().find(|(_, cube)| goal(cube)) {
+            return Some(moves.simplify());
+        }
+        if done < depth {
+            level = level
+                .iter()
+                .flat_map(|(moves, cube)| {
+                    steps
+                        .iter()
+                        .map(|step| (moves.clone() + step, cube.apply(step.clone())))
+                })
+                .collect();
+        }
+    }
+    None
+}
+
+fn corner_solved(cube: &Cube, position: usize) -> bool {
+    cube.corner_pieces()[position] == (position, 0)
+}
+
+fn edge_solved(cube: &Cube, position: usize) -> bool {
+    cube.edge_pieces()[position] == (position, false)
+}
+
+fn cross_solved(cube: &Cube) -> bool {
+    (8..12).all(|position| edge_solved(cube, position))
+}
+
+fn first_layer_solved(cube: &Cube) -> bool {
+    cross_solved(cube) && FIRST_LAYER_CORNERS.iter().all(|&p| corner_solved(cube, p))
+}
+
+fn first_two_layers_solved(cube: &Cube) -> bool {
+    first_layer_solved(cube) && MIDDLE_EDGES.iter().all(|&p| edge_solved(cube, p))
+}
+
+fn yellow_cross_solved(cube: &Cube) -> bool {
+    first_two_layers_solved(cube) && cube.edge_pieces()[..4].iter().all(|&(_, flipped)| !flipped)
+}
+
+fn yellow_edges_solved(cube: &Cube) -> bool {
+    first_two_layers_solved(cube) && (0..4).all(|position| edge_solved(cube, position))
+}
+
+fn yellow_corners_placed(cube: &Cube) -> bool {
+    yellow_edges_solved(cube)
+        && [0, 1, 6, 7]
+            .into_iter()
+            .all(|position| cube.corner_pieces()[position].0 == position)
+}
+
+fn name(color: Side) -> String {
+    format!("{color:?}").to_lowercase()
+}
+
+/// The colors of the piece that belongs on `faces`, like "white, red and green"
+fn colors(cube: &Cube, faces: &[Face]) -> String {
+    let names: Vec<String> = faces.iter().map(|&face| name(cube.center(face))).collect();
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Solves a piece with one of `inserts`, or takes it out with one of `pops` first when none of
+/// them work, returning whether it had to be taken out
+fn insert(
+    cube: &Cube,
+    inserts: &[Algorithm],
+    pops: &[Algorithm],
+    solved: impl Fn(&Cube) -> bool,
+    popped: impl Fn(&Cube) -> bool,
+) -> Option<(Algorithm, bool)> {
+    if let Some(moves) = search(cube, inserts, 1, &solved) {
+        return Some((moves, false));
+    }
+    let out = search(cube, pops, 1, popped)?;
+    let rest = search(&cube.apply(out.clone()), inserts, 1, &solved)?;
+    Some(((out + &rest).simplify(), true))
+}
+
+struct Solver {
+    cube: Cube,
+    steps: Vec<Step>,
+}
+
+impl Solver {
+    fn push(&mut self, stage: Stage, explanation: String, moves: Algorithm) {
+        if moves == Algorithm::new() {
+            return;
+        }
+        self.cube = self.cube.apply(moves.clone());
+        self.steps.push(Step {
+            stage,
+            explanation,
+            moves,
+        });
+    }
+
+    fn top(&self) -> String {
+        name(self.cube.center(Face::U))
+    }
+
+    fn cross(&mut self) -> Option<()> {
+        let rotation = rotation_to_bottom(self.cube.face_of(Side::White));
+        let rotated =
+            rotation.map_or_else(|| self.cube.clone(), |r| self.cube.apply(vec![r].into()));
+        let turns = cross::solve_bottom(&rotated).into_iter().next()?;
+        let moves: Algorithm = rotation
+            .map(Move::Rotation)
+            .into_iter()
+            .chain(turns.into_iter().map(Move::FaceTurn))
+            .collect::<Vec<_>>()
+            .into();
+        let explanation = if rotation.is_some() {
+            "Hold the cube with white on the bottom, then solve the white cross, matching each edge \
+             with the center beside it"
+        } else {
+            "Solve the white cross on the bottom, matching each edge with the center beside it"
+        };
+        self.push(Stage::Cross, explanation.to_string(), moves);
+        Some(())
+    }
+
+    fn first_layer_corners(&mut self) -> Option<()> {
+        let sexy = Algorithm::sexy();
+        let inserts = after_top_turns(
+            &(1..=5)
+                .flat_map(|n| from_every_side(&(sexy.clone() * n)))
+                .collect::<Vec<_>>(),
+        );
+        let pops = from_every_side(&sexy);
+        for (i, &corner) in FIRST_LAYER_CORNERS.iter().enumerate() {
+            let kept = |cube: &Cube| {
+                cross_solved(cube)
+                    && FIRST_LAYER_CORNERS[..i]
+                        .iter()
+                        .all(|&p| corner_solved(cube, p))
+            };
+            let (moves, popped) = insert(
+                &self.cube,
+                &inserts,
+                &pops,
+                |cube| kept(cube) && corner_solved(cube, corner),
+                |cube| {
+                    kept(cube)
+                        && cube
+                            .corner_pieces()
+                            .iter()
+                            .position(|&(home, _)| home == corner)
+                            .is_some_and(|position| CORNER_FACES[position][0] == Face::U)
+                },
+            )?;
+            let colors = colors(&self.cube, &CORNER_FACES[corner]);
+            let explanation = if popped {
+                format!(
+                    "Take the {colors} corner out of the bottom layer with R U R' U', then bring \
+                     it above its place and repeat R U R' U' facing its place until it goes in"
+                )
+            } else {
+                format!(
+                    "Bring the {colors} corner above its place, then repeat R U R' U' facing its \
+                     place until it goes in"
+                )
+            };
+            self.push(Stage::FirstLayerCorners, explanation, moves);
+        }
+        Some(())
+    }
+
+    fn middle_layer(&mut self) -> Option<()> {
+        let right = algorithm(RIGHT_INSERT);
+        let inserts = after_top_turns(
+            &[right.clone(), algorithm(LEFT_INSERT)]
+                .iter()
+                .flat_map(from_every_side)
+                .collect::<Vec<_>>(),
+        );
+        let pops = from_every_side(&right);
+        for (i, &edge) in MIDDLE_EDGES.iter().enumerate() {
+            let kept = |cube: &Cube| {
+                first_layer_solved(cube) && MIDDLE_EDGES[..i].iter().all(|&p| edge_solved(cube, p))
+            };
+            let (moves, popped) = insert(
+                &self.cube,
+                &inserts,
+                &pops,
+                |cube| kept(cube) && edge_solved(cube, edge),
+                |cube| {
+                    kept(cube)
+                        && cube
+                            .edge_pieces()
+                            .iter()
+                            .position(|&(home, _)| home == edge)
+                            .is_some_and(|position| position < 4)
+                },
+            )?;
+            let colors = colors(&self.cube, &EDGE_FACES[edge]);
+            let explanation = if popped {
+                format!(
+                    "Take the {colors} edge out of the middle layer, then line it up with its \
+                     center and insert it to the side it belongs on"
+                )
+            } else {
+                format!(
+                    "Line the {colors} edge up with its center, then insert it to the side it \
+                     belongs on"
+                )
+            };
+            self.push(Stage::MiddleLayer, explanation, moves);
+        }
+        Some(())
+    }
+
+    fn yellow_cross(&mut self) -> Option<()> {
+        let moves = search(
+            &self.cube,
+            &after_top_turns(&[algorithm(EDGE_FLIP)]),
+            3,
+            yellow_cross_solved,
+        )?;
+        let explanation = format!(
+            "Make a {} cross on top by doing {EDGE_FLIP}, holding any line or L shape so it \
+             becomes a cross",
+            self.top()
+        );
+        self.push(Stage::YellowCross, explanation, moves);
+        Some(())
+    }
+
+    fn yellow_edges(&mut self) -> Option<()> {
+        let mut steps = after_top_turns(&[algorithm(SUNE)]);
+        steps.extend(top_turns().into_iter().skip(1));
+        let moves = search(&self.cube, &steps, 4, yellow_edges_solved)?;
+        let explanation = format!(
+            "Match each {} edge with the center beside it, using {SUNE} to swap them around",
+            self.top()
+        );
+        self.push(Stage::YellowEdges, explanation, moves);
+        Some(())
+    }
+
+    fn yellow_corner_positions(&mut self) -> Option<()> {
+        let moves = search(
+            &self.cube,
+            &from_every_side(&algorithm(NIKLAS)),
+            3,
+            yellow_corners_placed,
+        )?;
+        let explanation = format!(
+            "Put each {} corner in its place with {NIKLAS}, holding a corner that is already \
+             right at the front right",
+            self.top()
+        );
+        self.push(Stage::YellowCornerPositions, explanation, moves);
+        Some(())
+    }
+
+    fn yellow_corner_orientation(&mut self) -> Option<()> {
+        let twist = algorithm(CORNER_TWIST) * 2;
+        let top = algorithm("U");
+        let mut cube = self.cube.clone();
+        let mut moves = Algorithm::new();
+        for _ in 0..4 {
+            // each two goes of the twist turns the corner a third of the way around
+            for _ in 0..2 {
+                if cube.corner_pieces()[0].1 != 0 {
+                    moves = moves + &twist;
+                    cube = cube.apply(twist.clone());
+                }
+            }
+            moves = moves + &top;
+            cube = cube.apply(top.clone());
+        }
+        let moves = moves.simplify();
+        if !(0..8).all(|position| corner_solved(&cube, position)) || !yellow_edges_solved(&cube) {
+            return None;
+        }
+        let explanation = format!(
+            "Twist each {} corner by repeating {CORNER_TWIST} at the front right until it faces \
+             up, then turn the top to bring the next one there",
+            self.top()
+        );
+        self.push(Stage::YellowCornerOrientation, explanation, moves);
+        Some(())
+    }
+}
+
+/// Solves `cube` layer by layer, returning each step with an explanation of what it does
+///
+/// The white cross is solved on the bottom, starting with a rotation when white isn't already
+/// there. Steps that would have no moves, because that part is already solved, are left out.
+/// This returns [`None`] when the cube can't be solved, like when a corner has been twisted in
+/// place
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     solvers::beginner::{self, Stage},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+/// let steps = beginner::solve(&cube).unwrap();
+///
+/// // putting the corner back in with R U R' U' solves everything else too
+/// assert_eq!(steps.len(), 1);
+/// assert_eq!(steps[0].stage, Stage::FirstLayerCorners);
+/// assert!(steps[0].moves.solves(&Algorithm::from("R U R' U'").unwrap()));
+/// ```
+#[must_use]
+pub fn solve(cube: &Cube) -> Option<Vec<Step>> {
+    let mut solver = Solver {
+        cube: cube.clone(),
+        steps: Vec::new(),
+    };
+    solver.cross()?;
+    solver.first_layer_corners()?;
+    solver.middle_layer()?;
+    solver.yellow_cross()?;
+    solver.yellow_edges()?;
+    solver.yellow_corner_positions()?;
+    solver.yellow_corner_orientation()?;
+    Some(solver.steps)
+}
+
+#[cfg(test)]
+mod beginner_tests {
+    use super::*;
+    use crate::solvers::solvers_tests::random_scrambles;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    const SCRAMBLES: [&str; 3] = [
+        "R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F",
+        "D2 B2 L2 U' F2 D R2 U' B2 U2 R' D' B' U R' B' L F' D' R",
+        "x y R U2 F' L D B2",
+    ];
+
+    /// Checks that each step's stage is done once its moves are, and that the first two layers
+    /// are built by the last step of their stages
+    fn check(scramble: &Algorithm) {
+        let mut cube = Cube::new().apply(scramble.clone());
+        let steps = solve(&cube).unwrap();
+        for (i, step) in steps.iter().enumerate() {
+            cube = cube.apply(step.moves.clone());
+            let last = steps.get(i + 1).is_none_or(|next| next.stage != step.stage);
+            let done = match step.stage {
+                Stage::FirstLayerCorners if last => first_layer_solved(&cube),
+                Stage::MiddleLayer if last => first_two_layers_solved(&cube),
+                Stage::Cross | Stage::FirstLayerCorners | Stage::MiddleLayer => cross_solved(&cube),
+                Stage::YellowCross => yellow_cross_solved(&cube),
+                Stage::YellowEdges => yellow_edges_solved(&cube),
+                Stage::YellowCornerPositions => yellow_corners_placed(&cube),
+                Stage::YellowCornerOrientation => {
+                    (0..8).all(|position| corner_solved(&cube, position))
+                }
+            };
+            assert!(done, "{scramble}: {step:?}");
+        }
+        // the cube can end up rotated, so only the pieces are compared
+        assert!(yellow_edges_solved(&cube), "{scramble}");
+        assert!((0..8).all(|position| corner_solved(&cube, position)));
+        assert!(steps.windows(2).all(|pair| pair[0].stage <= pair[1].stage));
+    }
+
+    #[test]
+    fn solves_scrambles() {
+        for scramble in SCRAMBLES {
+            check(&Algorithm::from(scramble).unwrap());
+        }
+    }
+
+    #[test]
+    fn solves_random_scrambles() {
+        for scramble in random_scrambles(48, 20) {
+            check(&scramble);
+        }
+    }
+
+    #[test]
+    fn solved_cube_needs_no_steps() {
+        assert_eq!(solve(&Cube::new()), Some(Vec::new()));
+    }
+
+    #[test]
+    fn white_is_brought_to_the_bottom() {
+        let cube = Cube::new().apply(Algorithm::from("x2").unwrap());
+        let steps = solve(&cube).unwrap();
+
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].stage, Stage::Cross);
+        assert_eq!(steps[0].moves, Algorithm::from("x2").unwrap());
+    }
+
+    #[test]
+    fn explanations_name_the_pieces() {
+        let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+        let steps = solve(&cube).unwrap();
+
+        assert_eq!(steps[0].stage, Stage::FirstLayerCorners);
+        assert!(
+            steps[0].explanation.contains("white, red and green corner"),
+            "{}",
+            steps[0].explanation
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Finds every optimal solution to the cross, for any color
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{cube::Side, moves::Algorithm, solvers::cross};
+//!
+//! let scramble = Algorithm::from("R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F").unwrap();
+//!
+//! for solution in cross::solve(&scramble, Side::White) {
+//!     println!("{solution}");
+//! }
+//! ```
+use std::sync::OnceLock;
+
+use crate::{
+    cube::{Cube, Side},
+    moves::{Algorithm, FaceTurn, Move},
+};
+
+use super::{distances, edge_moves, rotation_to_bottom, unrotate, Step, FACE_TURNS};
+
+/// The positions of the edges of the D cross
+const CROSS_EDGES: [usize; 4] = [8, 9, 10, 11];
+
+/// Where each cross edge is, and whether it is flipped
+pub(super) type CrossState = [(usize, bool); 4];
+
+const SOLVED: CrossState = [(8, false), (9, false), (10, false), (11, false)];
+
+const fn index(state: CrossState) -> usize {
+    let mut index = 0;
+    let mut i = 0;
+    while i < 4 {
+        index = index * 24 + state[i].0 * 2 + state[i].1 as usize;
+        i += 1;
+    }
+    index
+}
+
+const fn state(mut index: usize) -> CrossState {
+    let mut state = SOLVED;
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        state[i] = ((index % 24) / 2, index % 2 == 1);
+        index /= 24;
+    }
+    state
+}
+
+pub(super) fn turn(state: CrossState, turn: usize) -> CrossState {
+    let table = &edge_moves()[turn];
+    state.map(|(position, flipped)| {
+        let (position, flip) = table[position];
+        (position, flipped ^ flip)
+    })
+}
+
+fn turn_index(index: usize, m: usize) -> usize {
+    self::index(turn(state(index), m))
+}
+
+/// The number of moves needed to solve each D cross state, found by searching out from solved
+fn cross_distances() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let turns = (0..FACE_TURNS.len()).collect::<Vec<_>>();
+        distances(24usize.pow(4), &[index(SOLVED)], &turns, turn_index)
+    })
+}
+
+/// The fewest moves that solve the D cross from `state`
+pub(super) fn distance(state: CrossState) -> u8 {
+    cross_distances()[index(state)]
+}
+
+pub(super) fn cross_state(cube: &Cube) -> CrossState {
+    let pieces = cube.edge_pieces();
+    CROSS_EDGES.map(|edge| {
+        pieces
+            .iter()
+            .enumerate()
+            .find_map(|(position, &(home, flipped))| (home == edge).then_some((position, flipped)))
+            .expect("every edge is somewhere")
+    })
+}
+
+/// Solves the cross on the bottom of `cube`
+pub(super) fn solve_bottom(cube: &Cube) -> Vec<Vec<FaceTurn>> {
+    let step = Step {
+        distances: cross_distances(),
+        turns: (0..FACE_TURNS.len()).collect(),
+        turn: turn_index,
+    };
+    step.solutions(index(cross_state(cube)), usize::MAX)
+}
+
+/// Finds every optimal sequence of face turns that solves the `color` cross after `scramble`
+///
+/// No rotations are used, so the cross is solved wherever its center already is
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Side, moves::Algorithm, solvers::cross};
+///
+/// let scramble = Algorithm::from("U F2 L").unwrap();
+/// let solutions = cross::solve(&scramble, Side::White);
+///
+/// assert!(solutions.contains(&Algorithm::from("L' F2").unwrap()));
+/// assert!(solutions.contains(&Algorithm::from("F2 L'").unwrap()));
+/// ```
+#[must_use]
+pub fn solve(scramble: &Algorithm, color: Side) -> Vec<Algorithm> {
+    let cube = Cube::new().apply(scramble.clone());
+    let rotation = rotation_to_bottom(cube.face_of(color));
+    let cube = rotation.map_or_else(|| cube.clone(), |r| cube.apply(vec![r].into()));
+    solve_bottom(&cube)
+        .into_iter()
+        .map(|turns| {
+            turns
+                .into_iter()
+                .map(|t| rotation.map_or(t, |r| unrotate(t, r)))
+                .collect::<Vec<_>>()
+                .into()
+        })
+        .collect()
+}
+
+/// Finds every optimal solution to the `color` cross after `scramble`, rotating first so it is on the bottom
+///
+/// Each solution starts with x2, x, x', z, or z' unless the cross is already on the bottom
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Side, moves::Algorithm, solvers::cross};
+///
+/// let scramble = Algorithm::from("R").unwrap();
+/// let solutions = cross::solve_with_rotation(&scramble, Side::Yellow);
+///
+/// assert_eq!(solutions, vec![Algorithm::from("x2 R'").unwrap()]);
+/// ```
+#[must_use]
+pub fn solve_with_rotation(scramble: &Algorithm, color: Side) -> Vec<Algorithm> {
+    let cube = Cube::new().apply(scramble.clone());
+    let rotation = rotation_to_bottom(cube.face_of(color));
+    let cube = rotation.map_or_else(|| cube.clone(), |r| cube.apply(vec![r].into()));
+    solve_bottom(&cube)
+        .into_iter()
+        .map(|turns| {
+            rotation
+                .map(Move::Rotation)
+                .into_iter()
+                .chain(turns.into_iter().map(Move::FaceTurn))
+                .collect::<Vec<_>>()
+                .into()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod cross_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    const COLORS: [Side; 6] = [
+        Side::Yellow,
+        Side::White,
+        Side::Red,
+        Side::Orange,
+        Side::Blue,
+        Side::Green,
+    ];
+
+    fn scramble() -> Algorithm {
+        Algorithm::from("R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F")
+            .unwrap()
+    }
+
+    #[test]
+    fn solved_cross_needs_no_moves() {
+        let actual = solve(&Algorithm::new(), Side::White);
+        let expected = vec![Algorithm::new()];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn F_is_solved_by_F_rev() {
+        let scramble = Algorithm::from("F").unwrap();
+        let actual = solve(&scramble, Side::White);
+        let expected = vec![Algorithm::from("F'").unwrap()];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn every_color_is_solved_optimally() {
+        for color in COLORS {
+            let solutions = solve(&scramble(), color);
+            assert!(!solutions.is_empty());
+            let length = solutions[0].moves.len();
+            assert!(length <= 8);
+            for solution in solutions {
+                assert_eq!(solution.moves.len(), length);
+                let cube = Cube::new().apply(scramble() + &solution);
+                assert!(cube.is_cross_solved(color), "{solution} for {color:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn solutions_are_all_different() {
+        let solutions = solve(&scramble(), Side::Red);
+        for (i, a) in solutions.iter().enumerate() {
+            for b in &solutions[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn rotated_solutions_put_the_cross_on_the_bottom() {
+        for color in COLORS {
+            let plain = solve(&scramble(), color);
+            let rotated = solve_with_rotation(&scramble(), color);
+            assert_eq!(plain.len(), rotated.len());
+            for solution in rotated {
+                let cube = Cube::new().apply(scramble() + &solution);
+                assert!(cube.is_cross_solved(color));
+                assert_eq!(cube.face_of(color), crate::cube::Face::D);
+            }
+        }
+    }
+
+    #[test]
+    fn rotations_in_the_scramble_are_followed() {
+        let scramble = Algorithm::from("x2 F").unwrap();
+        let actual = solve(&scramble, Side::Yellow);
+        let expected = vec![Algorithm::from("F'").unwrap()];
+
+        assert_eq!(actual, expected);
+    }
+}
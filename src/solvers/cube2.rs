@@ -0,0 +1,221 @@
+//! Finds optimal solutions for the 2x2 cube
+//!
+//! Solutions only use R, U, and F, so the DBL corner never moves
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{cube2::Cube2, moves::Algorithm, solvers::cube2};
+//!
+//! let cube = Cube2::new().apply(Algorithm::from("R U2 F' R2 U").unwrap());
+//! let solution = cube2::solve(&cube);
+//!
+//! assert!(cube.apply(solution).is_solved());
+//! ```
+use std::sync::OnceLock;
+
+use rand::Rng;
+
+use crate::{
+    coordinates::{permutation_from_index, permutation_index},
+    cube::{Center, Cube, CORNER_FACES},
+    cube2::Cube2,
+    moves::{Algorithm, FaceTurn},
+};
+
+use super::{corner_moves, distances, Deepening, FACE_TURNS};
+
+/// The DBL corner, which is left where it is
+const FIXED: usize = 5;
+
+/// Which corner is in each position, and how it is twisted
+type State = [(usize, u8); 8];
+
+fn turns() -> impl Iterator<Item = usize> {
+    FACE_TURNS
+        .iter()
+        .enumerate()
+        .filter(|(_, turn)| matches!(turn, FaceTurn::U(_) | FaceTurn::R(_) | FaceTurn::F(_)))
+        .map(|(m, _)| m)
+}
+
+fn turn(state: &State, turn: usize) -> State {
+    let table = &corner_moves()[turn];
+    let mut moved = *state;
+    for (position, &(piece, twisted)) in state.iter().enumerate() {
+        let (to, twist) = table[position];
+        moved[to] = (piece, (twisted + twist) % 3);
+    }
+    moved
+}
+
+fn permutation(state: &State) -> usize {
+    permutation_index(&state.map(|(piece, _)| piece))
+}
+
+fn twist(state: &State) -> usize {
+    state
+        .iter()
+        .fold(0, |index, &(_, twist)| index * 3 + usize::from(twist))
+}
+
+fn turn_permutation(index: usize, m: usize) -> usize {
+    let pieces = permutation_from_index::<8>(index);
+    permutation(&turn(&pieces.map(|piece| (piece, 0)), m))
+}
+
+fn turn_twist(mut index: usize, m: usize) -> usize {
+    let mut state: State = std::array::from_fn(|position| (position, 0));
+    for (_, twist) in state.iter_mut().rev() {
+        *twist = u8::try_from(index % 3).expect("twists are below 3");
+        index /= 3;
+    }
+    self::twist(&turn(&state, m))
+}
+
+/// The number of moves needed to put every corner in place, and to orient every corner
+fn tables() -> &'static (Vec<u8>, Vec<u8>) {
+    static TABLES: OnceLock<(Vec<u8>, Vec<u8>)> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let turns = turns().collect::<Vec<_>>();
+        (
+            distances((1..=8).product(), &[0], &turns, turn_permutation),
+            distances(3usize.pow(8), &[0], &turns, turn_twist),
+        )
+    })
+}
+
+fn distance(state: &State) -> u8 {
+    let (permutations, twists) = tables();
+    permutations[permutation(state)].max(twists[twist(state)])
+}
+
+/// Reads the state of the corners, using the colors of the DBL corner to decide where the others belong
+fn state(cube: &Cube2) -> State {
+    let fixed = cube.corners[FIXED].colors;
+    let mut centers = Cube::new().centers;
+    for (face, color) in CORNER_FACES[FIXED].into_iter().zip(fixed) {
+        let opposite = cube
+            .corners
+            .iter()
+            .flat_map(|corner| corner.colors)
+            .find(|&other| {
+                other != color
+                    && !cube.corners.iter().any(|corner| {
+                        corner.colors.contains(&color) && corner.colors.contains(&other)
+                    })
+            })
+            .expect("every color has an opposite");
+        centers[face as usize] = Center { color };
+        centers[face.opposite() as usize] = Center { color: opposite };
+    }
+    Cube {
+        corners: cube.corners,
+        centers,
+        ..Cube::new()
+    }
+    .corner_pieces()
+}
+
+/// Finds one of the shortest [`Algorithm`]s that solves `cube`
+///
+/// # Panics
+///
+/// This never panics, as every 2x2 state can be solved in 11 moves
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube2::Cube2, moves::Algorithm, solvers::cube2};
+///
+/// let cube = Cube2::new().apply(Algorithm::from("R U").unwrap());
+///
+/// assert_eq!(cube2::solve(&cube), Algorithm::from("U' R'").unwrap());
+/// ```
+#[must_use]
+pub fn solve(cube: &Cube2) -> Algorithm {
+    let deepening = Deepening {
+        turns: &turns().collect::<Vec<_>>(),
+        turn: &turn,
+        heuristic: &distance,
+    };
+    deepening
+        .shortest(&[state(cube)])
+        .expect("every 2x2 state can be solved")
+        .into_iter()
+        .map(|m| FACE_TURNS[m])
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Creates a scramble that leaves the cube in a random state, with every state equally likely
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube2::Cube2, solvers::cube2};
+///
+/// let scramble = cube2::scramble(&mut rand::thread_rng());
+/// let cube = Cube2::new().apply(scramble);
+///
+/// assert!(cube.apply(cube2::solve(&cube)).is_solved());
+/// ```
+#[must_use]
+pub fn scramble<R: Rng + ?Sized>(rng: &mut R) -> Algorithm {
+    solve(&Cube2::random(rng)).inverse()
+}
+
+#[cfg(test)]
+mod cube2_solver_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn solved_needs_no_moves() {
+        let actual = solve(&Cube2::new());
+        let expected = Algorithm::new();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn random_states_are_solved() {
+        let mut rng = StdRng::seed_from_u64(28);
+        for _ in 0..5 {
+            let cube = Cube2::random(&mut rng);
+            let solution = solve(&cube);
+
+            assert!(solution.moves.len() <= 11);
+            assert!(cube.apply(solution).is_solved());
+        }
+    }
+
+    #[test]
+    fn any_scheme_is_solved() {
+        let cube = Cube2::new_with_scheme(crate::color_scheme::ColorScheme::japanese())
+            .apply(Algorithm::from("R U2 F' L D").unwrap());
+
+        assert!(cube.apply(solve(&cube)).is_solved());
+    }
+
+    #[test]
+    fn left_turns_are_solved_with_right_turns() {
+        let cube = Cube2::new().apply(Algorithm::from("L").unwrap());
+        let actual = solve(&cube);
+        let expected = Algorithm::from("R'").unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn scrambles_reach_their_state() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let scramble = scramble(&mut rng);
+        let cube = Cube2::new().apply(scramble.clone());
+
+        assert!(!cube.is_solved());
+        assert!(cube.apply(scramble.inverse()).is_solved());
+    }
+}
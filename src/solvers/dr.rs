@@ -0,0 +1,481 @@
+//! Finds edge orientation (EO) and domino reduction (DR), the first steps of fewest moves solving
+//!
+//! A cube is in DR on an axis when it can be solved with quarter turns of the two faces on that
+//! axis and half turns of the others, like `<U, D, R2, L2, F2, B2>` for the U/D axis. That needs
+//! every corner twisted so its U or D color faces along the axis, every edge oriented, and the
+//! four edges that belong between the axis' faces somewhere in that slice.
+//!
+//! Edges are oriented to an axis when they can be solved without quarter turns of the faces on
+//! that axis, so EO on F/B is kept by `<U, D, L, R, F2, B2>`. DR is found after EO on one of the
+//! other axes, only using turns that keep it
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{
+//!     cube::Cube,
+//!     moves::Algorithm,
+//!     solvers::dr::{self, Axis},
+//! };
+//!
+//! let scramble = Algorithm::from("R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F").unwrap();
+//! let cube = Cube::new().apply(scramble);
+//! let (eo, dr) = dr::solve(&cube, Axis::UD);
+//!
+//! assert!(dr::is_dr(&cube.apply(eo + &dr), Axis::UD));
+//! ```
+use std::sync::OnceLock;
+
+use crate::{
+    cube::{Cube, Face},
+    moves::{Algorithm, FaceTurn, Rotation},
+};
+
+use super::{corner_moves, distances, edge_moves, unrotate, Step, FACE_TURNS};
+
+/// The number of ways to twist the corners, when the last one is set by the others
+const TWISTS: usize = 2187;
+
+/// The number of ways to place the four slice edges among the twelve positions
+const SLICES: usize = 495;
+
+/// The positions of the E slice, between U and D
+const SLICE: usize = 0b1111_0000;
+
+/// The three axes of the cube, each running between two opposite faces
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Axis {
+    UD,
+    FB,
+    RL,
+}
+
+impl Axis {
+    pub const ALL: [Self; 3] = [Self::UD, Self::FB, Self::RL];
+
+    const fn of(face: Face) -> Self {
+        match face {
+            Face::U | Face::D => Self::UD,
+            Face::F | Face::B => Self::FB,
+            Face::L | Face::R => Self::RL,
+        }
+    }
+}
+
+/// The rotations that turn the cube so that `ud` becomes the U/D axis, and `fb` becomes the F/B
+/// axis
+fn frame(ud: Option<Axis>, fb: Option<Axis>) -> Vec<Rotation> {
+    let quarters = [Rotation::X(1), Rotation::Y(1), Rotation::Z(1)];
+    std::iter::once(Vec::new())
+        .chain(quarters.map(|rotation| vec![rotation]))
+        .chain(
+            quarters
+                .into_iter()
+                .flat_map(|first| quarters.map(|second| vec![first, second])),
+        )
+        .find(|rotations| {
+            Face::ALL.into_iter().all(|face| {
+                let from = Some(Axis::of(face));
+                let to = Axis::of(rotations.iter().fold(face, |face, &r| face.rotate(r)));
+                (from != ud || to == Axis::UD) && (from != fb || to == Axis::FB)
+            })
+        })
+        .expect("two quarter rotations can take any two axes anywhere")
+}
+
+fn turned(cube: &Cube, frame: &[Rotation]) -> Cube {
+    cube.apply(frame.to_vec().into())
+}
+
+/// Converts turns done after the rotations of `frame` into the same turns done without them
+fn unturned(turns: Vec<FaceTurn>, frame: &[Rotation]) -> Algorithm {
+    turns
+        .into_iter()
+        .map(|turn| frame.iter().rev().fold(turn, |turn, &r| unrotate(turn, r)))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Counts the edges that are not oriented to `axis`
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     solvers::dr::{self, Axis},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("F").unwrap());
+///
+/// assert_eq!(dr::bad_edges(&cube, Axis::FB), 4);
+/// assert_eq!(dr::bad_edges(&cube, Axis::UD), 0);
+/// ```
+#[must_use]
+pub fn bad_edges(cube: &Cube, axis: Axis) -> usize {
+    turned(cube, &frame(None, Some(axis)))
+        .edge_orientation()
+        .iter()
+        .filter(|&&oriented| !oriented)
+        .count()
+}
+
+/// Determines if every edge is oriented to `axis`
+#[must_use]
+pub fn is_eo(cube: &Cube, axis: Axis) -> bool {
+    bad_edges(cube, axis) == 0
+}
+
+/// Counts the corners whose U or D color doesn't face along `axis`
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     solvers::dr::{self, Axis},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R").unwrap());
+///
+/// assert_eq!(dr::twisted_corners(&cube, Axis::UD), 4);
+/// assert_eq!(dr::twisted_corners(&cube, Axis::RL), 0);
+/// ```
+#[must_use]
+pub fn twisted_corners(cube: &Cube, axis: Axis) -> usize {
+    turned(cube, &frame(Some(axis), None))
+        .corner_pieces()
+        .iter()
+        .filter(|(_, twist)| *twist != 0)
+        .count()
+}
+
+/// Determines if the cube can be solved with quarter turns of the faces on `axis`, and half turns
+/// of the others
+#[must_use]
+pub fn is_dr(cube: &Cube, axis: Axis) -> bool {
+    let cube = turned(cube, &frame(Some(axis), None));
+    dr_index(&cube) == 0 && cube.edge_pieces().iter().all(|(_, flipped)| !flipped)
+}
+
+/// Which edges are flipped, one bit for each position
+pub(super) fn eo_index(cube: &Cube) -> usize {
+    cube.edge_pieces()
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, flipped))| *flipped)
+        .fold(0, |index, (position, _)| index | 1 << position)
+}
+
+pub(super) fn turn_eo(index: usize, turn: usize) -> usize {
+    edge_moves()[turn]
+        .iter()
+        .enumerate()
+        .fold(0, |moved, (position, &(to, flip))| {
+            let flipped = index >> position & 1 == 1;
+            moved | usize::from(flipped ^ flip) << to
+        })
+}
+
+/// The number of moves needed to orient the edges to F/B from each set of flipped edges
+fn eo_distances() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let turns: Vec<usize> = (0..FACE_TURNS.len()).collect();
+        distances(1 << 12, &[0], &turns, turn_eo)
+    })
+}
+
+/// Every face turn except quarter turns of F and B, which keep the edges oriented to F/B
+fn dr_turns() -> Vec<usize> {
+    FACE_TURNS
+        .iter()
+        .enumerate()
+        .filter(|(_, turn)| !matches!(turn, FaceTurn::F(1 | 3) | FaceTurn::B(1 | 3)))
+        .map(|(m, _)| m)
+        .collect()
+}
+
+/// Every set of four positions the slice edges can be in, with one bit for each position, and the
+/// index of each set
+fn slice_ranks() -> &'static (Vec<usize>, Vec<usize>) {
+    static TABLE: OnceLock<(Vec<usize>, Vec<usize>)> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        // the solved slice comes first, so that it has index 0
+        let mut masks = vec![SLICE];
+        masks.extend((0..1 << 12).filter(|&mask: &usize| mask.count_ones() == 4 && mask != SLICE));
+        let mut ranks = vec![0; 1 << 12];
+        for (rank, &mask) in masks.iter().enumerate() {
+            ranks[mask] = rank;
+        }
+        (masks, ranks)
+    })
+}
+
+fn twists(index: usize) -> [u8; 8] {
+    let mut twists = [0; 8];
+    let mut rest = index;
+    for twist in twists.iter_mut().take(7) {
+        *twist = u8::try_from(rest % 3).expect("twists are below 3");
+        rest /= 3;
+    }
+    twists[7] = (3 - twists.iter().sum::<u8>() % 3) % 3;
+    twists
+}
+
+fn twist_index(twists: [u8; 8]) -> usize {
+    twists[..7]
+        .iter()
+        .rev()
+        .fold(0, |index, &twist| index * 3 + usize::from(twist))
+}
+
+fn turn_dr(index: usize, turn: usize) -> usize {
+    let (masks, ranks) = slice_ranks();
+    let (twist, slice) = (index / SLICES, index % SLICES);
+    let mut moved = [0; 8];
+    for (position, twist) in twists(twist).into_iter().enumerate() {
+        let (to, added) = corner_moves()[turn][position];
+        moved[to] = (twist + added) % 3;
+    }
+    let mask = masks[slice];
+    let moved_mask = edge_moves()[turn]
+        .iter()
+        .enumerate()
+        .filter(|(position, _)| mask >> position & 1 == 1)
+        .fold(0, |moved, (_, &(to, _))| moved | 1 << to);
+    twist_index(moved) * SLICES + ranks[moved_mask]
+}
+
+/// Where the corners are twisted and the slice edges are, which is 0 when the cube is in DR on U/D
+fn dr_index(cube: &Cube) -> usize {
+    let (_, ranks) = slice_ranks();
+    let pieces = cube.edge_pieces();
+    let mask = pieces
+        .iter()
+        .enumerate()
+        .filter(|(_, (home, _))| SLICE >> home & 1 == 1)
+        .fold(0, |mask, (position, _)| mask | 1 << position);
+    let twists = cube.corner_pieces().map(|(_, twist)| twist);
+    twist_index(twists) * SLICES + ranks[mask]
+}
+
+/// The number of moves needed to reach DR on U/D from each index, keeping EO on F/B
+fn dr_distances() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| distances(TWISTS * SLICES, &[0], &dr_turns(), turn_dr))
+}
+
+/// Finds up to `limit` optimal paths from `index`, turned back into moves from outside `frame`
+fn solve_in(step: &Step, index: usize, frame: &[Rotation], limit: usize) -> Vec<Algorithm> {
+    step.solutions(index, limit)
+        .into_iter()
+        .map(|turns| unturned(turns, frame))
+        .collect()
+}
+
+/// Finds every optimal sequence of face turns that orients the edges to `axis`
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     solvers::dr::{self, Axis},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R F").unwrap());
+///
+/// assert_eq!(
+///     dr::eo(&cube, Axis::FB),
+///     vec![Algorithm::from("F").unwrap(), Algorithm::from("F'").unwrap()]
+/// );
+/// ```
+#[must_use]
+pub fn eo(cube: &Cube, axis: Axis) -> Vec<Algorithm> {
+    let frame = frame(None, Some(axis));
+    let step = Step {
+        distances: eo_distances(),
+        turns: (0..FACE_TURNS.len()).collect(),
+        turn: turn_eo,
+    };
+    solve_in(&step, eo_index(&turned(cube, &frame)), &frame, usize::MAX)
+}
+
+/// The frame for DR on `axis` keeping EO on `eo`, and the fewest moves that need
+fn dr_distance(cube: &Cube, axis: Axis, eo: Axis) -> (Vec<Rotation>, usize, u8) {
+    let frame = frame(Some(axis), Some(eo));
+    let index = dr_index(&turned(cube, &frame));
+    (frame, index, dr_distances()[index])
+}
+
+/// Finds every optimal sequence of face turns that reaches DR on `axis`, keeping the edges
+/// oriented
+///
+/// The edges need to be oriented to one of the other two axes first, and nothing is found when
+/// they aren't. When they are oriented to both, the turns that keep either are tried
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     solvers::dr::{self, Axis},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("U R").unwrap());
+///
+/// assert_eq!(
+///     dr::dr(&cube, Axis::UD),
+///     vec![Algorithm::from("R").unwrap(), Algorithm::from("R'").unwrap()]
+/// );
+/// ```
+#[must_use]
+pub fn dr(cube: &Cube, axis: Axis) -> Vec<Algorithm> {
+    let found: Vec<_> = Axis::ALL
+        .into_iter()
+        .filter(|&eo| eo != axis && is_eo(cube, eo))
+        .map(|eo| (eo, dr_distance(cube, axis, eo).2))
+        .collect();
+    let Some(shortest) = found.iter().map(|(_, distance)| *distance).min() else {
+        return Vec::new();
+    };
+    let mut solutions: Vec<Algorithm> = found
+        .into_iter()
+        .filter(|(_, distance)| *distance == shortest)
+        .flat_map(|(eo, _)| dr_keeping(cube, axis, eo, usize::MAX))
+        .collect();
+    solutions.sort();
+    solutions.dedup();
+    solutions
+}
+
+/// Finds up to `limit` optimal sequences of face turns that reach DR on `axis`, only using turns
+/// that keep EO on `eo`, which is empty when the edges aren't oriented to `eo`
+pub(super) fn dr_keeping(cube: &Cube, axis: Axis, eo: Axis, limit: usize) -> Vec<Algorithm> {
+    if !is_eo(cube, eo) {
+        return Vec::new();
+    }
+    let (frame, index, _) = dr_distance(cube, axis, eo);
+    let step = Step {
+        distances: dr_distances(),
+        turns: dr_turns(),
+        turn: turn_dr,
+    };
+    solve_in(&step, index, &frame, limit)
+}
+
+/// Finds a short way to DR on `axis`, as an optimal EO followed by an optimal DR from there
+///
+/// Every optimal EO on both of the other axes is tried, and the one with the shortest DR after it
+/// is used
+#[must_use]
+pub fn solve(cube: &Cube, axis: Axis) -> (Algorithm, Algorithm) {
+    let (eo, _) = Axis::ALL
+        .into_iter()
+        .filter(|&eo| eo != axis)
+        .flat_map(|eo_axis| {
+            eo(cube, eo_axis).into_iter().map(move |eo| {
+                let after = cube.apply(eo.clone());
+                let (_, _, distance) = dr_distance(&after, axis, eo_axis);
+                let length = eo.moves.len() + usize::from(distance);
+                (eo, length)
+            })
+        })
+        .min_by_key(|(eo, length)| (*length, eo.clone()))
+        .unwrap_or_default();
+    let after = cube.apply(eo.clone());
+    let dr = dr(&after, axis).into_iter().next().unwrap_or_default();
+    (eo, dr)
+}
+
+#[cfg(test)]
+mod dr_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn scramble() -> Cube {
+        Cube::new().apply(
+            Algorithm::from(
+                "R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F",
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn solved_cube_is_in_dr_on_every_axis() {
+        for axis in Axis::ALL {
+            assert!(is_eo(&Cube::new(), axis));
+            assert!(is_dr(&Cube::new(), axis));
+            assert_eq!(eo(&Cube::new(), axis), vec![Algorithm::new()]);
+            assert_eq!(dr(&Cube::new(), axis), vec![Algorithm::new()]);
+        }
+    }
+
+    #[test]
+    fn quarter_turns_leave_dr_on_their_axis() {
+        let cube = Cube::new().apply(Algorithm::from("R L' U2 F2 R").unwrap());
+
+        assert!(is_dr(&cube, Axis::RL));
+        assert!(!is_dr(&cube, Axis::UD));
+        assert!(!is_dr(&cube, Axis::FB));
+        assert_eq!(twisted_corners(&cube, Axis::RL), 0);
+        assert_eq!(bad_edges(&cube, Axis::UD), 0);
+        assert_eq!(bad_edges(&cube, Axis::FB), 0);
+        assert_ne!(bad_edges(&cube, Axis::RL), 0);
+    }
+
+    #[test]
+    fn eo_is_optimal_on_every_axis() {
+        let cube = scramble();
+        for axis in Axis::ALL {
+            let solutions = eo(&cube, axis);
+            assert!(!solutions.is_empty());
+            let length = solutions[0].moves.len();
+            assert!(length <= 7);
+            for solution in solutions {
+                assert_eq!(solution.moves.len(), length);
+                assert!(is_eo(&cube.apply(solution.clone()), axis), "{solution}");
+            }
+        }
+    }
+
+    #[test]
+    fn dr_keeps_the_edges_oriented() {
+        let cube = scramble();
+        for axis in [Axis::UD, Axis::RL] {
+            let oriented = cube.apply(eo(&cube, Axis::FB)[0].clone());
+            let solutions = dr(&oriented, axis);
+            assert!(!solutions.is_empty());
+            for solution in solutions {
+                let after = oriented.apply(solution.clone());
+                assert!(is_dr(&after, axis), "{solution}");
+                assert!(is_eo(&after, Axis::FB), "{solution}");
+            }
+        }
+    }
+
+    #[test]
+    fn dr_needs_oriented_edges() {
+        let cube = Cube::new().apply(Algorithm::from("F R").unwrap());
+
+        assert_eq!(dr(&cube, Axis::UD), Vec::<Algorithm>::new());
+    }
+
+    #[test]
+    fn solve_reaches_dr_on_every_axis() {
+        let cube = scramble();
+        for axis in Axis::ALL {
+            let (eo, dr) = solve(&cube, axis);
+            let after = cube.apply(eo.clone() + &dr);
+
+            assert!(is_dr(&after, axis), "{eo} {dr}");
+        }
+    }
+}
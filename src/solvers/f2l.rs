@@ -0,0 +1,324 @@
+//! Finds the shortest ways to insert an F2L pair, without breaking the cross or any solved pairs
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{
+//!     cube::{Cube, Slot},
+//!     moves::Algorithm,
+//!     solvers::f2l::{self, Moves},
+//! };
+//!
+//! let cube = Cube::new().apply(Algorithm::from("R U R' U'").unwrap());
+//!
+//! for solution in f2l::solve(&cube, Slot::FR, Moves::All) {
+//!     println!("{solution}");
+//! }
+//! ```
+use std::sync::OnceLock;
+
+use crate::{
+    cube::{Cube, Slot, CORNER_FACES},
+    moves::{Algorithm, FaceTurn, Move, Rotation},
+};
+
+use super::{
+    corner_moves,
+    cross::{self, CrossState},
+    distances, edge_moves, Deepening, FACE_TURNS,
+};
+
+/// No F2L pair needs more moves than this, even when the cross also has to be solved
+const MAX_DEPTH: u8 = 14;
+
+/// Which moves the F2L solver may use
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Moves {
+    /// Any face turn
+    All,
+    /// Only R, U, L, and D turns, after a y or y' if that is shorter
+    ///
+    /// A y2 is never tried, since it would only swap R and L
+    RULD,
+}
+
+/// Where the corner of a pair is and how it is twisted, then where the edge is and if it is flipped
+type PairState = ((usize, u8), (usize, bool));
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    cross: CrossState,
+    pairs: [PairState; 4],
+}
+
+impl State {
+    fn new(cube: &Cube) -> Self {
+        let corners = cube.corner_pieces();
+        let edges = cube.edge_pieces();
+        let corner = |home: usize| {
+            corners
+                .iter()
+                .enumerate()
+                .find_map(|(position, &(piece, twist))| {
+                    (piece == home).then_some((position, twist))
+                })
+                .expect("every corner is somewhere")
+        };
+        let edge = |home: usize| {
+            edges
+                .iter()
+                .enumerate()
+                .find_map(|(position, &(piece, flipped))| {
+                    (piece == home).then_some((position, flipped))
+                })
+                .expect("every edge is somewhere")
+        };
+        Self {
+            cross: cross::cross_state(cube),
+            pairs: Slot::ALL.map(|slot| (corner(slot.corner()), edge(slot.edge()))),
+        }
+    }
+
+    fn turn(self, turn: usize) -> Self {
+        Self {
+            cross: cross::turn(self.cross, turn),
+            pairs: self.pairs.map(|pair| turn_pair(pair, turn)),
+        }
+    }
+}
+
+fn turn_pair(((corner, twisted), (edge, flipped)): PairState, turn: usize) -> PairState {
+    let (corner, twist) = corner_moves()[turn][corner];
+    let (edge, flip) = edge_moves()[turn][edge];
+    ((corner, (twisted + twist) % 3), (edge, flipped ^ flip))
+}
+
+const fn pair_index(((corner, twist), (edge, flipped)): PairState) -> usize {
+    (corner * 3 + twist as usize) * 24 + edge * 2 + flipped as usize
+}
+
+fn turn_pair_index(index: usize, turn: usize) -> usize {
+    let corner = (
+        index / 72,
+        u8::try_from(index / 24 % 3).expect("twists are below 3"),
+    );
+    let edge = (index % 24 / 2, index % 2 == 1);
+    pair_index(turn_pair((corner, edge), turn))
+}
+
+/// The number of moves needed to solve each pair on its own, for each slot
+fn pair_distances() -> &'static [Vec<u8>; 4] {
+    static TABLE: OnceLock<[Vec<u8>; 4]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let turns: Vec<usize> = (0..FACE_TURNS.len()).collect();
+        Slot::ALL.map(|slot| {
+            let solved = pair_index(((slot.corner(), 0), (slot.edge(), false)));
+            distances(8 * 3 * 12 * 2, &[solved], &turns, turn_pair_index)
+        })
+    })
+}
+
+/// One way of looking at the cube, with the moves that are allowed from there
+struct Frame {
+    rotation: Option<Rotation>,
+    start: State,
+    goal: [bool; 4],
+    turns: Vec<usize>,
+}
+
+impl Frame {
+    fn new(cube: &Cube, slot: Slot, rotation: Option<Rotation>, turns: Vec<usize>) -> Self {
+        let cube = rotation.map_or_else(|| cube.clone(), |r| cube.apply(vec![r].into()));
+        let slot = rotation.map_or(slot, |r| rotate_slot(slot, r));
+        Self {
+            rotation,
+            start: State::new(&cube),
+            goal: Slot::ALL.map(|s| s == slot || cube.is_pair_solved(s)),
+            turns,
+        }
+    }
+
+    /// A lower bound on the moves needed to reach the goal, which is exact once it reaches 0
+    fn distance(&self, state: &State) -> u8 {
+        let tables = pair_distances();
+        (0..4)
+            .filter(|&i| self.goal[i])
+            .map(|i| tables[i][pair_index(state.pairs[i])])
+            .fold(cross::distance(state.cross), u8::max)
+    }
+
+    /// Every way of reaching the goal in exactly `depth` moves
+    fn search(&self, depth: u8) -> Vec<Algorithm> {
+        let deepening = Deepening {
+            turns: &self.turns,
+            turn: &|state: &State, m| state.turn(m),
+            heuristic: &|state| self.distance(state),
+        };
+        deepening
+            .solutions(&self.start, depth, usize::MAX)
+            .into_iter()
+            .map(|turns| {
+                self.rotation
+                    .map(Move::Rotation)
+                    .into_iter()
+                    .chain(turns.into_iter().map(|m| Move::FaceTurn(FACE_TURNS[m])))
+                    .collect::<Vec<_>>()
+                    .into()
+            })
+            .collect()
+    }
+
+    /// Whether every edge that has to be solved can be, as R, U, L, and D never flip edges
+    fn can_solve_without_flipping(&self) -> bool {
+        let cross_edges = self.start.cross.iter().map(|&(_, flipped)| flipped);
+        let pair_edges = (0..4)
+            .filter(|&i| self.goal[i])
+            .map(|i| self.start.pairs[i].1 .1);
+        !cross_edges.chain(pair_edges).any(|flipped| flipped)
+    }
+}
+
+/// The slot that `slot` is in after `rotation`
+fn rotate_slot(slot: Slot, rotation: Rotation) -> Slot {
+    let faces = CORNER_FACES[slot.corner()].map(|face| face.rotate(rotation));
+    Slot::ALL
+        .into_iter()
+        .find(|s| {
+            faces
+                .iter()
+                .all(|face| CORNER_FACES[s.corner()].contains(face))
+        })
+        .expect("rotations around y keep slots as slots")
+}
+
+/// Finds every shortest way to solve the F2L pair in `slot`
+///
+/// The cross on the bottom, and any pairs that are already solved, are kept solved. If the cross
+/// isn't solved, it gets solved along with the pair. When only [`Moves::RULD`] are allowed some
+/// cases can't be solved, and no solutions are returned
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::{Cube, Slot},
+///     moves::Algorithm,
+///     solvers::f2l::{self, Moves},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R U' R'").unwrap());
+/// let solutions = f2l::solve(&cube, Slot::FR, Moves::All);
+///
+/// assert_eq!(solutions, vec![Algorithm::from("R U R'").unwrap()]);
+/// ```
+#[must_use]
+pub fn solve(cube: &Cube, slot: Slot, moves: Moves) -> Vec<Algorithm> {
+    let frames: Vec<Frame> = match moves {
+        Moves::All => vec![Frame::new(cube, slot, None, (0..18).collect())],
+        Moves::RULD => {
+            let turns: Vec<usize> = FACE_TURNS
+                .iter()
+                .enumerate()
+                .filter(|(_, turn)| {
+                    matches!(
+                        turn,
+                        FaceTurn::R(_) | FaceTurn::U(_) | FaceTurn::L(_) | FaceTurn::D(_)
+                    )
+                })
+                .map(|(m, _)| m)
+                .collect();
+            [None, Some(Rotation::Y(1)), Some(Rotation::Y(3))]
+                .into_iter()
+                .map(|rotation| Frame::new(cube, slot, rotation, turns.clone()))
+                .filter(Frame::can_solve_without_flipping)
+                .collect()
+        }
+    };
+    (0..=MAX_DEPTH)
+        .map(|depth| {
+            frames
+                .iter()
+                .flat_map(|frame| frame.search(depth))
+                .collect::<Vec<_>>()
+        })
+        .find(|solutions| !solutions.is_empty())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod f2l_tests {
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn check(cube: &Cube, slot: Slot, solutions: &[Algorithm]) {
+        let kept: Vec<Slot> = Slot::ALL
+            .into_iter()
+            .filter(|&s| cube.is_pair_solved(s))
+            .collect();
+        assert!(!solutions.is_empty());
+        for solution in solutions {
+            let solved = cube.apply(solution.clone());
+            assert!(solved.is_cross_solved(solved.center(crate::cube::Face::D)));
+            assert!(solved.is_pair_solved(rotate_any(&solved, cube, slot)));
+            for &s in &kept {
+                assert!(solved.is_pair_solved(rotate_any(&solved, cube, s)));
+            }
+        }
+    }
+
+    /// Finds the slot in `after` that holds the same centers as `slot` does in `before`
+    fn rotate_any(after: &Cube, before: &Cube, slot: Slot) -> Slot {
+        let colors = CORNER_FACES[slot.corner()].map(|face| before.center(face));
+        Slot::ALL
+            .into_iter()
+            .find(|s| {
+                colors
+                    .iter()
+                    .all(|&color| CORNER_FACES[s.corner()].contains(&after.face_of(color)))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn solved_pair_needs_no_moves() {
+        let actual = solve(&Cube::new(), Slot::BL, Moves::All);
+        let expected = vec![Algorithm::new()];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sexy_move_is_undone() {
+        let cube = Cube::new().apply(Algorithm::sexy());
+        let solutions = solve(&cube, Slot::FR, Moves::All);
+
+        check(&cube, Slot::FR, &solutions);
+        assert!(solutions.contains(&Algorithm::from("U R U' R'").unwrap()));
+    }
+
+    #[test]
+    fn other_solved_pairs_are_kept() {
+        let cube = Cube::new().apply(Algorithm::from("R U R' U2").unwrap());
+        let solutions = solve(&cube, Slot::FR, Moves::All);
+
+        check(&cube, Slot::FR, &solutions);
+        assert!(solutions.contains(&Algorithm::from("U2 R U' R'").unwrap()));
+    }
+
+    #[test]
+    fn back_slots_use_a_rotation_when_only_ruld_is_allowed() {
+        let cube = Cube::new().apply(Algorithm::from("B U B'").unwrap());
+        let solutions = solve(&cube, Slot::BR, Moves::RULD);
+
+        check(&cube, Slot::BR, &solutions);
+        assert!(solutions.contains(&Algorithm::from("y R U' R'").unwrap()));
+    }
+
+    #[test]
+    fn flipped_pairs_have_no_ruld_solution() {
+        let cube = Cube::new().apply(Algorithm::from("F R F' R'").unwrap());
+
+        assert!(solve(&cube, Slot::FR, Moves::RULD).is_empty());
+    }
+}
@@ -0,0 +1,256 @@
+//! Searches for [`Algorithm`](crate::moves::Algorithm)s that bring a cube closer to solved
+use std::sync::OnceLock;
+
+use crate::{
+    cube::{Cube, Face},
+    moves::{FaceTurn, Rotation},
+};
+
+pub mod beginner;
+pub mod cross;
+pub mod cube2;
+pub mod dr;
+pub mod f2l;
+pub mod roux;
+pub mod thistlethwaite;
+pub mod zz;
+
+/// Every face turn, three for each face
+pub(crate) const FACE_TURNS: [FaceTurn; 18] = [
+    FaceTurn::U(1),
+    FaceTurn::U(2),
+    FaceTurn::U(3),
+    FaceTurn::D(1),
+    FaceTurn::D(2),
+    FaceTurn::D(3),
+    FaceTurn::F(1),
+    FaceTurn::F(2),
+    FaceTurn::F(3),
+    FaceTurn::B(1),
+    FaceTurn::B(2),
+    FaceTurn::B(3),
+    FaceTurn::L(1),
+    FaceTurn::L(2),
+    FaceTurn::L(3),
+    FaceTurn::R(1),
+    FaceTurn::R(2),
+    FaceTurn::R(3),
+];
+
+/// Determines if the turn at index `next` is worth trying after the one at `prev`
+///
+/// Indices are into [`FACE_TURNS`], or into a longer list that goes on with turns of `M` and of
+/// `r`, three for each layer. Turning the same layer twice in a row is never needed, and layers
+/// that share an axis are only turned in the order they are listed, which for face turns is the
+/// order that [`Algorithm::simplify`](crate::moves::Algorithm::simplify) leaves them in, so no
+/// two sequences that are searched do the same thing
+pub(crate) const fn can_follow(prev: usize, next: usize) -> bool {
+    // the layers are U, D, F, B, L, R, M and r
+    const AXES: [u8; 8] = [0, 0, 1, 1, 2, 2, 2, 2];
+    let (prev, next) = (prev / 3, next / 3);
+    AXES[prev] != AXES[next] || prev < next
+}
+
+/// For each of [`FACE_TURNS`], where the edge in each position is sent, and if it gets flipped
+pub(crate) fn edge_moves() -> &'static [[(usize, bool); 12]; 18] {
+    static TABLE: OnceLock<[[(usize, bool); 12]; 18]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        FACE_TURNS.map(|turn| {
+            let cube = Cube::new().apply(vec![turn].into());
+            let mut table = [(0, false); 12];
+            for (position, (home, flipped)) in cube.edge_pieces().into_iter().enumerate() {
+                table[home] = (position, flipped);
+            }
+            table
+        })
+    })
+}
+
+/// For each of [`FACE_TURNS`], where the corner in each position is sent, and how much it gets twisted
+pub(crate) fn corner_moves() -> &'static [[(usize, u8); 8]; 18] {
+    static TABLE: OnceLock<[[(usize, u8); 8]; 18]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        FACE_TURNS.map(|turn| {
+            let cube = Cube::new().apply(vec![turn].into());
+            let mut table = [(0, 0); 8];
+            for (position, (home, twist)) in cube.corner_pieces().into_iter().enumerate() {
+                table[home] = (position, twist);
+            }
+            table
+        })
+    })
+}
+
+/// The rotation that brings `face` to the bottom, if it isn't already there
+pub(crate) const fn rotation_to_bottom(face: Face) -> Option<Rotation> {
+    match face {
+        Face::U => Some(Rotation::X(2)),
+        Face::D => None,
+        Face::F => Some(Rotation::X(3)),
+        Face::B => Some(Rotation::X(1)),
+        Face::L => Some(Rotation::Z(3)),
+        Face::R => Some(Rotation::Z(1)),
+    }
+}
+
+/// Converts a turn done after `rotation` into the same turn done without it
+pub(crate) fn unrotate(turn: FaceTurn, rotation: Rotation) -> FaceTurn {
+    let (face, amount) = turn.face();
+    Face::ALL
+        .into_iter()
+        .find(|f| f.rotate(rotation) == face)
+        .expect("rotations move every face somewhere")
+        .turn(amount)
+}
+
+/// Searches out from the `solved` indices to find how many moves each index needs, where `turn`
+/// gives the index that each of `turns` leads to
+pub(crate) fn distances(
+    size: usize,
+    solved: &[usize],
+    turns: &[usize],
+    turn: fn(usize, usize) -> usize,
+) -> Vec<u8> {
+    let mut table = vec![u8::MAX; size];
+    for &index in solved {
+        table[index] = 0;
+    }
+    let mut frontier = solved.to_vec();
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for index in frontier {
+            for &m in turns {
+                let moved = turn(index, m);
+                if table[moved] == u8::MAX {
+                    table[moved] = depth;
+                    next.push(moved);
+                }
+            }
+        }
+        frontier = next;
+    }
+    table
+}
+
+/// A table of [`distances`], with the turns it was searched with
+pub(crate) struct Step {
+    pub(crate) distances: &'static [u8],
+    pub(crate) turns: Vec<usize>,
+    pub(crate) turn: fn(usize, usize) -> usize,
+}
+
+impl Step {
+    /// Finds up to `limit` of the optimal sequences of turns from `index` to a solved index
+    pub(crate) fn solutions(&self, index: usize, limit: usize) -> Vec<Vec<FaceTurn>> {
+        let mut solutions = Vec::new();
+        self.search(index, None, &mut Vec::new(), &mut solutions, limit);
+        solutions
+    }
+
+    /// Follows every move that gets one step closer, collecting each complete path
+    fn search(
+        &self,
+        index: usize,
+        prev: Option<usize>,
+        path: &mut Vec<FaceTurn>,
+        solutions: &mut Vec<Vec<FaceTurn>>,
+        limit: usize,
+    ) {
+        let distance = self.distances[index];
+        if distance == 0 {
+            solutions.push(path.clone());
+            return;
+        }
+        for &m in &self.turns {
+            if solutions.len() >= limit {
+                return;
+            }
+            let moved = (self.turn)(index, m);
+            if prev.is_none_or(|prev| can_follow(prev, m)) && self.distances[moved] < distance {
+                path.push(FACE_TURNS[m]);
+                self.search(moved, Some(m), path, solutions, limit);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// A search that goes deeper and deeper, guided by a `heuristic` that is a lower bound on the
+/// moves still needed and is zero only once the goal is reached
+pub(crate) struct Deepening<'a, S> {
+    pub(crate) turns: &'a [usize],
+    pub(crate) turn: &'a dyn Fn(&S, usize) -> S,
+    pub(crate) heuristic: &'a dyn Fn(&S) -> u8,
+}
+
+impl<S> Deepening<'_, S> {
+    /// Finds the shortest sequence of turns that reaches the goal from any of `starts`, as
+    /// indices of the turns
+    ///
+    /// Returns [`None`] if there isn't one within [`u8::MAX`] turns
+    pub(crate) fn shortest(&self, starts: &[S]) -> Option<Vec<usize>> {
+        (0..=u8::MAX).find_map(|depth| {
+            starts
+                .iter()
+                .find_map(|start| self.solutions(start, depth, 1).pop())
+        })
+    }
+
+    /// Finds up to `limit` of the sequences of exactly `depth` turns that reach the goal from
+    /// `start`, as indices of the turns
+    pub(crate) fn solutions(&self, start: &S, depth: u8, limit: usize) -> Vec<Vec<usize>> {
+        let mut solutions = Vec::new();
+        self.search(start, depth, &mut Vec::new(), &mut solutions, limit);
+        solutions
+    }
+
+    /// Follows every turn that could still reach the goal in time, collecting each complete path
+    fn search(
+        &self,
+        state: &S,
+        depth: u8,
+        path: &mut Vec<usize>,
+        solutions: &mut Vec<Vec<usize>>,
+        limit: usize,
+    ) {
+        if (self.heuristic)(state) > depth {
+            return;
+        }
+        if depth == 0 {
+            solutions.push(path.clone());
+            return;
+        }
+        for &m in self.turns {
+            if solutions.len() >= limit {
+                return;
+            }
+            if path.last().is_none_or(|&prev| can_follow(prev, m)) {
+                path.push(m);
+                self.search(&(self.turn)(state, m), depth - 1, path, solutions, limit);
+                path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod solvers_tests {
+    use super::*;
+    use crate::moves::Algorithm;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// `count` scrambles of 25 random face turns, the same ones every time for the same `seed`
+    pub fn random_scrambles(seed: u64, count: usize) -> Vec<Algorithm> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| {
+                (0..25)
+                    .map(|_| FACE_TURNS[rng.gen_range(0..FACE_TURNS.len())])
+                    .collect::<Vec<_>>()
+                    .into()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,960 @@
+//! Solves the cube with the Roux method, which builds a 1x2x3 block on each side and finishes
+//! with the last four corners and the six edges left in the U layer and the M slice
+//!
+//! The steps are the first block on the left, the second block on the right, CMLL for the
+//! corners, then the last six edges in three parts. EOLR orients the edges and brings UL and UR
+//! to the bottom, UL/UR puts those two in place, and 4c solves the last four edges of the M slice
+//! along with its centers
+//!
+//! The M slice can be turned at any time, so the blocks are judged against the centers the cube
+//! would have after whichever M turn lines them up
+use std::{fmt::Display, sync::OnceLock};
+
+use crate::{
+    coordinates::{permutation_from_index, permutation_index},
+    cube::{Cube, Face, Side},
+    moves::{Algorithm, LayerTurn, Move, SliceTurn},
+};
+
+use super::{distances, Deepening, FACE_TURNS};
+
+/// The faces of the M slice, in the order `M` moves its centers
+const M_RING: [Face; 4] = [Face::U, Face::F, Face::D, Face::B];
+
+/// The positions of the U layer corners, in the order `U` moves them
+const U_CORNERS: [usize; 4] = [0, 7, 6, 1];
+
+/// The positions of the last six edges, UR, UF, UL, UB, DF and DB
+const LAST_EDGES: [usize; 6] = [0, 1, 2, 3, 9, 11];
+
+/// The edges DL, FL and BL with the corners DFL and DBL
+const FIRST_BLOCK: ([usize; 3], [usize; 2]) = ([10, 6, 7], [4, 5]);
+
+/// The edges DR, FR and BR with the corners DFR and DBR
+const SECOND_BLOCK: ([usize; 3], [usize; 2]) = ([8, 5, 4], [3, 2]);
+
+/// The two 1x2x2 squares of each block, as two edges and the corner between them
+const SQUARES: [[usize; 3]; 4] = [[10, 6, 4], [10, 7, 5], [8, 5, 3], [8, 4, 2]];
+
+/// The number of ways to place the two edges and the corner of a square
+const SQUARE_SIZE: usize = 24 * 24 * 24;
+
+/// The index into [`turns`] of `U`, `U2` and `U'`
+const U_TURNS: [usize; 3] = [0, 1, 2];
+
+/// The index into [`turns`] of `M`, `M2` and `M'`
+const M_TURNS: [usize; 3] = [18, 19, 20];
+
+/// The moves the last six edges are solved with, as indices into [`turns`]
+const LSE_TURNS: [usize; 6] = [0, 1, 2, 18, 19, 20];
+
+/// The number of ways to arrange the last six edges, the M slice centers and the U layer
+const LSE_SIZE: usize = 720 * 64 * 4 * 4;
+
+/// How many `U` turns it takes to bring UFR to the U layer corner `position`
+fn ring(position: usize) -> usize {
+    U_CORNERS
+        .iter()
+        .position(|&p| p == position)
+        .expect("the blocks hold the other corners")
+}
+
+/// Where a move sends the piece in each position, and how it turns it
+struct Turn {
+    edges: [(usize, bool); 12],
+    corners: [(usize, u8); 8],
+    /// How many quarter turns the M slice centers move in the direction of `M`
+    centers: u8,
+}
+
+impl Turn {
+    fn of(moves: &Algorithm) -> Self {
+        let after = Cube::new().apply(moves.clone());
+        let centers = M_RING
+            .iter()
+            .position(|&face| face == after.face_of(Side::Yellow))
+            .expect("the moves only turn the M slice centers");
+        // judging the pieces against the centers they started with follows them absolutely
+        let held = Cube {
+            centers: Cube::new().centers,
+            ..after
+        };
+        let mut edges = [(0, false); 12];
+        for (position, (home, flipped)) in held.edge_pieces().into_iter().enumerate() {
+            edges[home] = (position, flipped);
+        }
+        let mut corners = [(0, 0); 8];
+        for (position, (home, twist)) in held.corner_pieces().into_iter().enumerate() {
+            corners[home] = (position, twist);
+        }
+        Self {
+            edges,
+            corners,
+            centers: u8::try_from(centers).expect("the ring has four faces"),
+        }
+    }
+}
+
+/// Every face turn, then the turns of `M` and of `r`, with where they send each piece
+fn turns() -> &'static [(Move, Turn)] {
+    static TABLE: OnceLock<Vec<(Move, Turn)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let slices = (1..4).map(|amount| Move::Slice(SliceTurn::M(amount)));
+        let wides = (1..4).map(|amount| {
+            Move::Layer(LayerTurn {
+                turn: Face::R.turn(amount),
+                depth: 2,
+                wide: true,
+            })
+        });
+        FACE_TURNS
+            .into_iter()
+            .map(Move::FaceTurn)
+            .chain(slices)
+            .chain(wides)
+            .map(|m| (m, Turn::of(&vec![m].into())))
+            .collect()
+    })
+}
+
+/// `M` turned `amount` times
+fn m_turns(amount: u8) -> Algorithm {
+    match amount % 4 {
+        0 => Algorithm::new(),
+        amount => vec![SliceTurn::M(amount)].into(),
+    }
+}
+
+/// Where each piece is and how it is turned, named by the position it belongs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pieces {
+    edges: [(usize, bool); 12],
+    corners: [(usize, u8); 8],
+    /// How many `M` turns the centers are from where the pieces are judged against
+    centers: u8,
+}
+
+impl Pieces {
+    /// Judges the pieces against the centers the cube would have after `alignment` `M` turns
+    fn of(cube: &Cube, alignment: u8) -> Self {
+        let aligned = cube.apply(m_turns(alignment));
+        let mut edges = [(0, false); 12];
+        for (position, (home, flipped)) in aligned.edge_pieces().into_iter().enumerate() {
+            edges[home] = (position, flipped);
+        }
+        let mut corners = [(0, 0); 8];
+        for (position, (home, twist)) in aligned.corner_pieces().into_iter().enumerate() {
+            corners[home] = (position, twist);
+        }
+        let pieces = Self {
+            edges,
+            corners,
+            centers: 0,
+        };
+        pieces.turn(&Turn::of(&m_turns(4 - alignment)))
+    }
+
+    fn turn(self, turn: &Turn) -> Self {
+        Self {
+            edges: self.edges.map(|(position, flipped)| {
+                let (to, flip) = turn.edges[position];
+                (to, flipped ^ flip)
+            }),
+            corners: self.corners.map(|(position, twisted)| {
+                let (to, twist) = turn.corners[position];
+                (to, (twisted + twist) % 3)
+            }),
+            centers: (self.centers + turn.centers) % 4,
+        }
+    }
+
+    fn has(&self, (edges, corners): ([usize; 3], [usize; 2])) -> bool {
+        edges.iter().all(|&edge| self.edges[edge] == (edge, false))
+            && corners
+                .iter()
+                .all(|&corner| self.corners[corner] == (corner, 0))
+    }
+
+    fn square(&self, [a, b, corner]: [usize; 3]) -> usize {
+        let edge = |edge: usize| self.edges[edge].0 * 2 + usize::from(self.edges[edge].1);
+        let (position, twist) = self.corners[corner];
+        (edge(a) * 24 + edge(b)) * 24 + position * 3 + usize::from(twist)
+    }
+
+    /// The piece in `position`, with its twist, for the U layer corners
+    fn corner_at(&self, position: usize) -> (usize, u8) {
+        (0..8)
+            .find(|&home| self.corners[home].0 == position)
+            .map(|home| (home, self.corners[home].1))
+            .expect("every position has a corner")
+    }
+}
+
+/// The pieces of a cube with the first block built, judged against the centers it is built on
+fn first_block_pieces(cube: &Cube) -> Option<Pieces> {
+    (0..4)
+        .map(|alignment| Pieces::of(cube, alignment))
+        .find(|pieces| pieces.has(FIRST_BLOCK))
+}
+
+fn block_pieces(cube: &Cube) -> Option<Pieces> {
+    first_block_pieces(cube).filter(|pieces| pieces.has(SECOND_BLOCK))
+}
+
+fn last_six(cube: &Cube) -> Option<LastSix> {
+    block_pieces(cube)
+        .filter(|pieces| cmll_case_of(pieces) == CmllCase::SOLVED)
+        .map(|pieces| LastSix::of(&pieces))
+}
+
+/// Determines if the first block is built on the left, with any M slice centers
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::roux};
+///
+/// let cube = Cube::new().apply(Algorithm::from("M' U R2").unwrap());
+///
+/// assert!(roux::is_first_block_solved(&cube));
+/// assert!(!roux::is_second_block_solved(&cube));
+/// ```
+#[must_use]
+pub fn is_first_block_solved(cube: &Cube) -> bool {
+    first_block_pieces(cube).is_some()
+}
+
+/// Determines if both blocks are built, sharing the same bottom color
+#[must_use]
+pub fn is_second_block_solved(cube: &Cube) -> bool {
+    block_pieces(cube).is_some()
+}
+
+/// Determines if both blocks are built and the last four corners are solved, up to a U turn
+#[must_use]
+pub fn is_cmll_solved(cube: &Cube) -> bool {
+    last_six(cube).is_some()
+}
+
+/// Determines if CMLL is solved, the last six edges are oriented, and UL and UR are at DF and DB
+#[must_use]
+pub fn is_eolr_solved(cube: &Cube) -> bool {
+    last_six(cube).is_some_and(|edges| edges.eolr())
+}
+
+/// Determines if CMLL is solved, the last six edges are oriented, and UL and UR are solved
+#[must_use]
+pub fn is_ulur_solved(cube: &Cube) -> bool {
+    last_six(cube).is_some_and(|edges| edges.ulur())
+}
+
+/// How the last four corners are turned, named by the shape the U colors make on top
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum CmllOrientation {
+    /// Every corner has its U color on top
+    O,
+    /// Every corner is twisted, with the U colors facing left and right in two pairs
+    H,
+    /// Every corner is twisted, with one pair facing the same way and the other facing apart
+    Pi,
+    /// Two corners next to each other are twisted, with their U colors facing the same way
+    U,
+    /// Two corners next to each other are twisted, with their U colors facing apart
+    T,
+    /// Three corners are twisted the way Sune solves
+    S,
+    /// Three corners are twisted the way anti-Sune solves
+    As,
+    /// Two opposite corners are twisted
+    L,
+}
+
+/// How the last four corners are placed once they are turned
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum CmllPermutation {
+    /// The corners are solved up to a U turn
+    Solved,
+    /// Two corners next to each other need swapping
+    Adjacent,
+    /// Two opposite corners need swapping
+    Diagonal,
+}
+
+/// The case the last four corners are in after both blocks are built
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct CmllCase {
+    pub orientation: CmllOrientation,
+    pub permutation: CmllPermutation,
+}
+
+impl CmllCase {
+    const SOLVED: Self = Self {
+        orientation: CmllOrientation::O,
+        permutation: CmllPermutation::Solved,
+    };
+}
+
+fn cmll_case_of(pieces: &Pieces) -> CmllCase {
+    let corners = U_CORNERS.map(|position| pieces.corner_at(position));
+    let twists = corners.map(|(_, twist)| twist);
+    let twisted = |i: usize| twists[i % 4] != 0;
+    let orientation = match twists.iter().filter(|&&twist| twist != 0).count() {
+        0 => CmllOrientation::O,
+        3 if twists.contains(&1) => CmllOrientation::As,
+        3 => CmllOrientation::S,
+        4 if twists[0] == twists[2] => CmllOrientation::H,
+        4 => CmllOrientation::Pi,
+        _ => match (0..4).find(|&i| twisted(i) && twisted(i + 1)) {
+            Some(i) if twists[i] == 1 => CmllOrientation::T,
+            Some(_) => CmllOrientation::U,
+            None => CmllOrientation::L,
+        },
+    };
+
+    // how far round the U layer each corner is from its home
+    let mut counts = [0; 4];
+    for (i, &(home, _)) in corners.iter().enumerate() {
+        counts[(ring(home) + 4 - i) % 4] += 1;
+    }
+    let permutation = match counts.iter().filter(|&&count| count > 0).count() {
+        1 => CmllPermutation::Solved,
+        2 => CmllPermutation::Diagonal,
+        _ => CmllPermutation::Adjacent,
+    };
+
+    CmllCase {
+        orientation,
+        permutation,
+    }
+}
+
+/// Recognizes the CMLL case, or returns [`None`] when the blocks aren't built
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     solvers::roux::{self, CmllOrientation, CmllPermutation},
+/// };
+///
+/// let sune = Algorithm::from("R U R' U R U2 R'").unwrap();
+/// let case = roux::cmll_case(&Cube::new().apply(sune.inverse())).unwrap();
+///
+/// assert_eq!(case.orientation, CmllOrientation::S);
+/// assert_eq!(case.permutation, CmllPermutation::Solved);
+/// ```
+#[must_use]
+pub fn cmll_case(cube: &Cube) -> Option<CmllCase> {
+    block_pieces(cube).map(|pieces| cmll_case_of(&pieces))
+}
+
+/// The last six edges, the M slice centers, and the U layer corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LastSix {
+    /// Which of [`LAST_EDGES`] is in each of their positions
+    edges: [usize; 6],
+    /// Whether the edge in each position is flipped
+    flips: [bool; 6],
+    centers: u8,
+    /// How many `U` turns the corners are from solved
+    corners: u8,
+}
+
+impl LastSix {
+    fn of(pieces: &Pieces) -> Self {
+        let mut edges = [0; 6];
+        let mut flips = [false; 6];
+        for (piece, &home) in LAST_EDGES.iter().enumerate() {
+            let (position, flipped) = pieces.edges[home];
+            let slot = LAST_EDGES
+                .iter()
+                .position(|&p| p == position)
+                .expect("the blocks hold the other edges");
+            edges[slot] = piece;
+            flips[slot] = flipped;
+        }
+        let corners = ring(pieces.corners[0].0);
+        Self {
+            edges,
+            flips,
+            centers: pieces.centers,
+            corners: u8::try_from(corners).expect("there are four U layer corners"),
+        }
+    }
+
+    fn turn(self, turn: &Turn) -> Self {
+        let mut moved = self;
+        for (slot, &position) in LAST_EDGES.iter().enumerate() {
+            let (to, flip) = turn.edges[position];
+            let to = LAST_EDGES
+                .iter()
+                .position(|&p| p == to)
+                .expect("U and M keep the last six edges apart");
+            moved.edges[to] = self.edges[slot];
+            moved.flips[to] = self.flips[slot] ^ flip;
+        }
+        let corner = turn.corners[U_CORNERS[usize::from(self.corners)]].0;
+        moved.corners = u8::try_from(ring(corner)).expect("there are four U layer corners");
+        moved.centers = (self.centers + turn.centers) % 4;
+        moved
+    }
+
+    fn index(&self) -> usize {
+        let flips = self
+            .flips
+            .iter()
+            .rev()
+            .fold(0, |bits, &flipped| bits * 2 + usize::from(flipped));
+        ((permutation_index(&self.edges) * 64 + flips) * 4 + usize::from(self.centers)) * 4
+            + usize::from(self.corners)
+    }
+
+    fn from_index(index: usize) -> Self {
+        let corners = u8::try_from(index % 4).expect("below four");
+        let centers = u8::try_from(index / 4 % 4).expect("below four");
+        let bits = index / 16 % 64;
+        Self {
+            edges: permutation_from_index(index / 1024),
+            flips: std::array::from_fn(|slot| bits >> slot & 1 == 1),
+            centers,
+            corners,
+        }
+    }
+
+    /// Whether the edges are oriented, which with the centers a quarter turn off means they
+    /// would be after one more M turn
+    fn oriented(&self) -> bool {
+        let even = if self.centers % 2 == 1 {
+            self.turn(&turns()[M_TURNS[0]].1)
+        } else {
+            *self
+        };
+        !even.flips.contains(&true)
+    }
+
+    /// Whether UL and UR are at DF and DB with every edge oriented, or already solved
+    fn eolr(&self) -> bool {
+        let mut bottom = [self.edges[4], self.edges[5]];
+        bottom.sort_unstable();
+        self.oriented() && bottom == [0, 2] || self.ulur()
+    }
+
+    fn ulur(&self) -> bool {
+        self.oriented() && self.edges[0] == 0 && self.edges[2] == 2 && self.corners == 0
+    }
+
+    fn solved(&self) -> bool {
+        self.index() == 0
+    }
+}
+
+/// For each of [`LSE_TURNS`], where it sends each ordering of the edges and each set of flips
+struct LseMoves {
+    orderings: Vec<[usize; 6]>,
+    flips: [[usize; 6]; 64],
+}
+
+fn lse_moves() -> &'static LseMoves {
+    static TABLE: OnceLock<LseMoves> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let moved = |index: usize, m: usize| {
+            LastSix::from_index(index)
+                .turn(&turns()[LSE_TURNS[m]].1)
+                .index()
+        };
+        LseMoves {
+            orderings: (0..720)
+                .map(|ordering| std::array::from_fn(|m| moved(ordering * 1024, m) / 1024))
+                .collect(),
+            flips: std::array::from_fn(|bits| {
+                std::array::from_fn(|m| moved(bits * 16, m) / 16 % 64)
+            }),
+        }
+    })
+}
+
+fn turn_last_six(index: usize, m: usize) -> usize {
+    let moves = lse_moves();
+    let turn = &turns()[LSE_TURNS[m]].1;
+    let centers = (index / 4 + usize::from(turn.centers)) % 4;
+    let corners = (index + ring(turn.corners[0].0)) % 4;
+    ((moves.orderings[index / 1024][m] * 64 + moves.flips[index / 16 % 64][m]) * 4 + centers) * 4
+        + corners
+}
+
+/// How many moves each arrangement of the last six edges needs to finish EOLR, UL/UR, and 4c
+fn lse_distances() -> &'static [Vec<u8>; 3] {
+    static TABLE: OnceLock<[Vec<u8>; 3]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut solved: [Vec<usize>; 3] = Default::default();
+        for index in 0..LSE_SIZE {
+            let edges = LastSix::from_index(index);
+            for (goal, reached) in [edges.eolr(), edges.ulur(), edges.solved()]
+                .into_iter()
+                .enumerate()
+            {
+                if reached {
+                    solved[goal].push(index);
+                }
+            }
+        }
+        solved.map(|solved| distances(LSE_SIZE, &solved, &[0, 1, 2, 3, 4, 5], turn_last_six))
+    })
+}
+
+/// How many moves each placement of a square needs, for each of [`SQUARES`]
+fn square_distances() -> &'static [Vec<u8>; 4] {
+    static TABLE: OnceLock<[Vec<u8>; 4]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let first: Vec<usize> = (0..24).collect();
+        let second = [U_TURNS, [15, 16, 17], M_TURNS, [21, 22, 23]].concat();
+        std::array::from_fn(|square| {
+            let solved = Pieces::of(&Cube::new(), 0).square(SQUARES[square]);
+            let turns = if square < 2 { &first } else { &second };
+            distances(SQUARE_SIZE, &[solved], turns, turn_square)
+        })
+    })
+}
+
+fn turn_square(index: usize, m: usize) -> usize {
+    let turn = &turns()[m].1;
+    let edge = |edge: usize| {
+        let (to, flip) = turn.edges[edge / 2];
+        to * 2 + usize::from((edge % 2 == 1) ^ flip)
+    };
+    let (to, twist) = turn.corners[index % 24 / 3];
+    let twist = (index % 3 + usize::from(twist)) % 3;
+    (edge(index / 576) * 24 + edge(index / 24 % 24)) * 24 + to * 3 + twist
+}
+
+/// Finds the shortest sequence of `turns` that brings `heuristic` down to zero from any of `starts`
+fn shortest(starts: &[Pieces], turns: &[usize], heuristic: fn(&Pieces) -> u8) -> Algorithm {
+    let deepening = Deepening {
+        turns,
+        turn: &|pieces: &Pieces, m| pieces.turn(&self::turns()[m].1),
+        heuristic: &heuristic,
+    };
+    deepening
+        .shortest(starts)
+        .expect("every block can be built")
+        .iter()
+        .map(|&m| self::turns()[m].0)
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Finds the shortest way to build the first block, with face turns and turns of `M` and `r`
+///
+/// The block is built on whichever centers make it shortest, and the moves are empty when it is
+/// already built
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::roux};
+///
+/// let cube = Cube::new().apply(Algorithm::from("R' F L2 r U' M").unwrap());
+/// let moves = roux::first_block(&cube);
+///
+/// assert!(roux::is_first_block_solved(&cube.apply(moves)));
+/// ```
+#[must_use]
+pub fn first_block(cube: &Cube) -> Algorithm {
+    let starts: Vec<Pieces> = (0..4)
+        .map(|alignment| Pieces::of(cube, alignment))
+        .collect();
+    shortest(&starts, &(0..24).collect::<Vec<_>>(), |pieces| {
+        let tables = square_distances();
+        tables[0][pieces.square(SQUARES[0])].max(tables[1][pieces.square(SQUARES[1])])
+    })
+}
+
+/// Finds the shortest way to build the second block with `<U, R, r, M>`, keeping the first
+///
+/// Returns [`None`] when the first block isn't built
+#[must_use]
+pub fn second_block(cube: &Cube) -> Option<Algorithm> {
+    let start = first_block_pieces(cube)?;
+    let turns = [U_TURNS, [15, 16, 17], M_TURNS, [21, 22, 23]].concat();
+    Some(shortest(&[start], &turns, |pieces| {
+        let tables = square_distances();
+        tables[2][pieces.square(SQUARES[2])].max(tables[3][pieces.square(SQUARES[3])])
+    }))
+}
+
+/// The algorithms CMLL is done with, each after any U turn
+const CMLL_ALGORITHMS: [[&str; 2]; 2] = [
+    // Sune and anti-Sune, for orienting the corners
+    ["R U R' U R U2 R'", "R U2 R' U' R U' R'"],
+    // the T and Y permutations, for swapping adjacent or diagonal corners
+    [
+        "R U R' U' R' F R2 U' R' U' R U R' F'",
+        "F R U' R' U' R U R' F' R U R' U' R' F R F'",
+    ],
+];
+
+/// Tries up to two of `algorithms`, each after any U turn, until `goal` is reached
+fn look(pieces: &Pieces, algorithms: [&str; 2], goal: fn(CmllCase) -> bool) -> Option<Algorithm> {
+    let steps: Vec<(Algorithm, Turn)> = ["", "U ", "U2 ", "U' "]
+        .into_iter()
+        .flat_map(|auf| algorithms.map(|moves| format!("{auf}{moves}")))
+        .map(|moves| {
+            let moves = Algorithm::from(&moves).expect("the algorithms are valid");
+            let turn = Turn::of(&moves);
+            (moves, turn)
+        })
+        .collect();
+    let mut tried = vec![(Algorithm::new(), *pieces)];
+    for _ in 0..=2 {
+        if let Some((moves, _)) = tried.iter().find(|(_, p)| goal(cmll_case_of(p))) {
+            return Some(moves.clone());
+        }
+        tried = tried
+            .iter()
+            .flat_map(|(moves, p)| {
+                steps
+                    .iter()
+                    .map(|(step, turn)| (moves.clone() + step, p.turn(turn)))
+            })
+            .collect();
+    }
+    None
+}
+
+/// Solves the last four corners in two looks, orienting them with Sune and anti-Sune then
+/// placing them with the T and Y permutations, and finishes with the U turn that lines them up
+///
+/// Returns [`None`] when the blocks aren't built
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::roux};
+///
+/// let cube = Cube::new().apply(Algorithm::from("R U R' U R U2 R' U").unwrap());
+/// let moves = roux::cmll(&cube).unwrap();
+///
+/// assert!(roux::is_cmll_solved(&cube.apply(moves)));
+/// ```
+#[must_use]
+pub fn cmll(cube: &Cube) -> Option<Algorithm> {
+    let pieces = block_pieces(cube)?;
+    let [orient, permute] = CMLL_ALGORITHMS;
+    let oriented = look(&pieces, orient, |case| {
+        case.orientation == CmllOrientation::O
+    })?;
+    let pieces = pieces.turn(&Turn::of(&oriented));
+    let permuted = look(&pieces, permute, |case| case == CmllCase::SOLVED)?;
+    let pieces = pieces.turn(&Turn::of(&permuted));
+    let auf = match LastSix::of(&pieces).corners {
+        0 => Algorithm::new(),
+        turns => vec![FACE_TURNS[usize::from(4 - turns) - 1]].into(),
+    };
+    Some((oriented + &permuted + &auf).simplify())
+}
+
+/// Follows the `goal` table of [`lse_distances`] down to zero, with `U` and `M` turns
+fn last_six_step(cube: &Cube, goal: usize) -> Option<Algorithm> {
+    let table = &lse_distances()[goal];
+    let mut index = last_six(cube)?.index();
+    if table[index] == u8::MAX {
+        return None;
+    }
+    let mut moves = Vec::new();
+    while table[index] > 0 {
+        let m = (0..LSE_TURNS.len())
+            .find(|&m| table[turn_last_six(index, m)] < table[index])
+            .expect("some turn gets closer");
+        moves.push(turns()[LSE_TURNS[m]].0);
+        index = turn_last_six(index, m);
+    }
+    Some(moves.into())
+}
+
+/// Finds the shortest way to orient the last six edges and bring UL and UR to DF and DB
+///
+/// Returns [`None`] when CMLL isn't solved
+#[must_use]
+pub fn eolr(cube: &Cube) -> Option<Algorithm> {
+    last_six_step(cube, 0)
+}
+
+/// Finds the shortest way to solve UL and UR and line up the corners, keeping the edges oriented
+///
+/// Returns [`None`] when CMLL isn't solved
+#[must_use]
+pub fn ulur(cube: &Cube) -> Option<Algorithm> {
+    last_six_step(cube, 1)
+}
+
+/// Finds the shortest way to solve the last edges and the M slice centers, which is called 4c
+/// when only the four M slice edges are left
+///
+/// Returns [`None`] when CMLL isn't solved
+#[must_use]
+pub fn last_edges(cube: &Cube) -> Option<Algorithm> {
+    last_six_step(cube, 2)
+}
+
+/// The moves of each Roux step, as they would be written in a reconstruction
+#[derive(Debug, PartialEq, Eq, Clone, Default, PartialOrd, Ord, Hash)]
+pub struct Solution {
+    pub first_block: Algorithm,
+    pub second_block: Algorithm,
+    pub cmll: Algorithm,
+    pub eolr: Algorithm,
+    pub ulur: Algorithm,
+    pub last_edges: Algorithm,
+}
+
+impl Solution {
+    /// Every step, named the way reconstructions name them
+    #[must_use]
+    pub const fn steps(&self) -> [(&'static str, &Algorithm); 6] {
+        [
+            ("first block", &self.first_block),
+            ("second block", &self.second_block),
+            ("CMLL", &self.cmll),
+            ("EOLR", &self.eolr),
+            ("UL/UR", &self.ulur),
+            ("4c", &self.last_edges),
+        ]
+    }
+
+    /// All the steps one after another
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.steps()
+            .into_iter()
+            .fold(Algorithm::new(), |all, (_, moves)| all + moves)
+    }
+}
+
+/// Writes a move the way Roux solvers do, with wide turns of two layers in lowercase, like `r`
+fn roux_notation(m: Move) -> String {
+    match m {
+        Move::Layer(
+            turn @ LayerTurn {
+                depth: 2,
+                wide: true,
+                ..
+            },
+        ) => turn.turn.to_string().to_lowercase(),
+        m => m.to_string(),
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, moves) in self.steps() {
+            let moves: Vec<String> = moves.moves.iter().copied().map(roux_notation).collect();
+            let line = format!("{} // {name}", moves.join(" "));
+            writeln!(f, "{}", line.trim_start())?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves the cube with the Roux method, one step at a time
+///
+/// Steps that are already done have no moves. The blocks can be built on any centers of the M
+/// slice, so the solved cube may end up turned round the x axis. Returns [`None`] when a step
+/// can't be finished, which only happens to cubes that can't be solved
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::roux};
+///
+/// let cube = Cube::new().apply(Algorithm::from("F2 U' L2 B R' D2 F U2 R").unwrap());
+/// let solution = roux::solve(&cube).unwrap();
+/// let solved = cube.apply(solution.algorithm());
+///
+/// assert!(roux::is_ulur_solved(&solved));
+/// assert_eq!(roux::last_edges(&solved), Some(Algorithm::new()));
+/// ```
+#[must_use]
+pub fn solve(cube: &Cube) -> Option<Solution> {
+    let first_block = first_block(cube);
+    let cube = cube.apply(first_block.clone());
+    let second_block = second_block(&cube)?;
+    let cube = cube.apply(second_block.clone());
+    let cmll = cmll(&cube)?;
+    let cube = cube.apply(cmll.clone());
+    let eolr = eolr(&cube)?;
+    let cube = cube.apply(eolr.clone());
+    let ulur = ulur(&cube)?;
+    let cube = cube.apply(ulur.clone());
+    let last_edges = last_edges(&cube)?;
+    Some(Solution {
+        first_block,
+        second_block,
+        cmll,
+        eolr,
+        ulur,
+        last_edges,
+    })
+}
+
+#[cfg(test)]
+mod roux_tests {
+    use super::*;
+    use crate::solvers::solvers_tests::random_scrambles;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    const SCRAMBLES: [&str; 3] = [
+        "R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F",
+        "D2 B2 L2 U' F2 D R2 U' B2 U2 R' D' B' U R' B' L F' D' R",
+        "x y R U2 F' L D B2 M' r",
+    ];
+
+    fn cube(moves: &str) -> Cube {
+        Cube::new().apply(Algorithm::from(moves).unwrap())
+    }
+
+    /// Whether every piece matches the centers, however the cube is turned
+    fn solved(cube: &Cube) -> bool {
+        cube.edge_pieces() == std::array::from_fn(|edge| (edge, false))
+            && cube.corner_pieces() == std::array::from_fn(|corner| (corner, 0))
+    }
+
+    /// Checks that each step reaches its goal, and that the steps solve the cube
+    fn check(scramble: &Algorithm) {
+        let start = Cube::new().apply(scramble.clone());
+        let solution = solve(&start).unwrap();
+        let goals: [fn(&Cube) -> bool; 6] = [
+            is_first_block_solved,
+            is_second_block_solved,
+            is_cmll_solved,
+            is_eolr_solved,
+            is_ulur_solved,
+            solved,
+        ];
+        let mut cube = start;
+        for ((name, moves), goal) in solution.steps().into_iter().zip(goals) {
+            cube = cube.apply(moves.clone());
+            assert!(goal(&cube), "{scramble}: {name}");
+        }
+        assert!(solved(&cube), "{scramble}");
+    }
+
+    #[test]
+    fn blocks_are_judged_against_any_m_slice_centers() {
+        assert!(is_first_block_solved(&cube("M' U R2")));
+        assert!(!is_second_block_solved(&cube("M' U R2")));
+        assert!(is_second_block_solved(&cube("U M' U2")));
+        assert!(!is_first_block_solved(&cube("F")));
+        // turning L with the M slice moves the block onto other centers
+        assert!(is_first_block_solved(&cube("L")));
+        assert!(is_cmll_solved(&cube("M2 U")));
+        assert!(is_ulur_solved(&cube("M'")));
+        assert!(!is_eolr_solved(&cube("M2 U")));
+    }
+
+    #[test]
+    fn cmll_cases_are_recognized() {
+        let recognized = |moves: &str| {
+            let case = cmll_case(&Cube::new().apply(Algorithm::from(moves).unwrap().inverse()));
+            case.map(|case| (case.orientation, case.permutation))
+        };
+        let [[sune, anti_sune], [t_perm, y_perm]] = CMLL_ALGORITHMS;
+
+        assert_eq!(
+            recognized(""),
+            Some((CmllOrientation::O, CmllPermutation::Solved))
+        );
+        assert_eq!(
+            recognized("U"),
+            Some((CmllOrientation::O, CmllPermutation::Solved))
+        );
+        assert_eq!(
+            recognized(sune),
+            Some((CmllOrientation::S, CmllPermutation::Solved))
+        );
+        assert_eq!(
+            recognized(anti_sune),
+            Some((CmllOrientation::As, CmllPermutation::Solved))
+        );
+        assert_eq!(
+            recognized(t_perm),
+            Some((CmllOrientation::O, CmllPermutation::Adjacent))
+        );
+        assert_eq!(
+            recognized(y_perm),
+            Some((CmllOrientation::O, CmllPermutation::Diagonal))
+        );
+        assert_eq!(
+            recognized("R U R' U R U' R' U R U2 R'").map(|(orientation, _)| orientation),
+            Some(CmllOrientation::H)
+        );
+        assert_eq!(
+            recognized("R U2 R2 U' R2 U' R2 U2 R").map(|(orientation, _)| orientation),
+            Some(CmllOrientation::Pi)
+        );
+        assert_eq!(
+            recognized("R2 D' R U2 R' D R U2 R"),
+            Some((CmllOrientation::U, CmllPermutation::Adjacent))
+        );
+        assert_eq!(
+            recognized("r U R' U' r' F R F'"),
+            Some((CmllOrientation::T, CmllPermutation::Adjacent))
+        );
+        assert_eq!(
+            recognized("F' r U R' U' r' F R"),
+            Some((CmllOrientation::L, CmllPermutation::Adjacent))
+        );
+        assert_eq!(
+            recognized(&format!("{sune} {t_perm}")),
+            Some((CmllOrientation::S, CmllPermutation::Adjacent))
+        );
+        assert_eq!(recognized("R"), None);
+    }
+
+    #[test]
+    fn solves_scrambles() {
+        for scramble in SCRAMBLES {
+            check(&Algorithm::from(scramble).unwrap());
+        }
+    }
+
+    #[test]
+    fn solves_random_scrambles() {
+        for scramble in random_scrambles(49, 10) {
+            check(&scramble);
+        }
+    }
+
+    #[test]
+    fn last_edges_are_found_optimally() {
+        assert_eq!(
+            last_edges(&cube("M2 U2 M2 U2")).map(|moves| moves.moves.len()),
+            Some(4)
+        );
+        assert_eq!(ulur(&cube("U2 M2")).map(|moves| moves.moves.len()), Some(1));
+        assert_eq!(eolr(&cube("R")), None);
+    }
+
+    #[test]
+    fn solved_cube_needs_no_moves() {
+        assert_eq!(solve(&Cube::new()), Some(Solution::default()));
+    }
+
+    #[test]
+    fn solutions_display_each_step_on_a_line() {
+        let solution = Solution {
+            first_block: Algorithm::from("R' F r r2 Rw'").unwrap(),
+            cmll: Algorithm::from("R U R' U R U2 R'").unwrap(),
+            last_edges: Algorithm::from("M2 U M' U2 M U M2").unwrap(),
+            ..Solution::default()
+        };
+
+        assert_str_eq!(
+            solution.to_string(),
+            "R' F r r2 r' // first block\n// second block\nR U R' U R U2 R' // CMLL\n// EOLR\n\
+             // UL/UR\nM2 U M' U2 M U M2 // 4c\n"
+        );
+    }
+}
@@ -0,0 +1,438 @@
+//! Solves the cube in Thistlethwaite's four phases, each moving it into a smaller group
+//!
+//! - G0 is every state, made by `<U, D, F, B, L, R>`
+//! - G1 is `<U, D, L, R, F2, B2>`, reached by orienting the edges to F/B
+//! - G2 is `<U, D, L2, R2, F2, B2>`, reached by orienting the corners to U/D and putting the E
+//!   slice edges in the E slice, which is domino reduction
+//! - G3 is `<U2, D2, L2, R2, F2, B2>`, reached by putting the other edges in their slices, and
+//!   the corners where half turns can solve them
+//! - G4 is the solved cube
+//!
+//! Each phase only uses the moves of the group it starts in, and is solved optimally, though the
+//! whole solution usually isn't
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::thistlethwaite};
+//!
+//! let scramble = Algorithm::from("R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F").unwrap();
+//! let cube = Cube::new().apply(scramble.clone());
+//! let phases = thistlethwaite::solve(&cube);
+//!
+//! for (group, phase) in phases.iter().enumerate() {
+//!     println!("G{group} to G{}: {phase}", group + 1);
+//! }
+//! let solution = phases.iter().fold(Algorithm::new(), |all, phase| all + phase);
+//! assert!(solution.solves(&scramble));
+//! ```
+use std::{collections::HashSet, sync::OnceLock};
+
+use crate::{
+    coordinates::{permutation_from_index, permutation_index},
+    cube::Cube,
+    moves::{Algorithm, FaceTurn},
+};
+
+use super::{
+    corner_moves, distances,
+    dr::{self, Axis},
+    edge_moves, Step, FACE_TURNS,
+};
+
+/// The number of orderings of the eight corners
+const CORNER_PERMUTATIONS: usize = 40320;
+
+/// The number of corner orderings that half turns can solve
+const HALF_TURN_CORNERS: usize = 96;
+
+/// The positions of the edges of each slice, M, S and E
+const SLICES: [[usize; 4]; 3] = [[1, 3, 9, 11], [0, 2, 8, 10], [4, 5, 6, 7]];
+
+/// The number of ways to place the four M slice edges among the eight U and D layer positions
+const M_PLACEMENTS: usize = 70;
+
+/// The number of ways to order the edges within their slices
+const SLICE_ORDERINGS: usize = 24 * 24 * 24;
+
+fn turns(keep: fn(FaceTurn) -> bool) -> Vec<usize> {
+    FACE_TURNS
+        .iter()
+        .enumerate()
+        .filter(|(_, &turn)| keep(turn))
+        .map(|(m, _)| m)
+        .collect()
+}
+
+/// The turns of G2
+fn g2_turns() -> Vec<usize> {
+    turns(|turn| matches!(turn, FaceTurn::U(_) | FaceTurn::D(_)) || turn.face().1 == 2)
+}
+
+/// The turns of G3
+fn g3_turns() -> Vec<usize> {
+    turns(|turn| turn.face().1 == 2)
+}
+
+/// Which corner each ordering of the corners leads to, for each of [`FACE_TURNS`]
+fn corner_table() -> &'static [[usize; 18]] {
+    static TABLE: OnceLock<Vec<[usize; 18]>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        (0..CORNER_PERMUTATIONS)
+            .map(|index| {
+                let corners: [usize; 8] = permutation_from_index(index);
+                let mut table = [0; 18];
+                for (m, moved) in table.iter_mut().enumerate() {
+                    let mut turned = [0; 8];
+                    for (position, &corner) in corners.iter().enumerate() {
+                        turned[corner_moves()[m][position].0] = corner;
+                    }
+                    *moved = permutation_index(&turned);
+                }
+                table
+            })
+            .collect()
+    })
+}
+
+/// Every corner ordering that half turns can solve, solved first, and the rank of each ordering
+/// in that list
+fn half_turn_corners() -> &'static (Vec<usize>, Vec<usize>) {
+    static TABLE: OnceLock<(Vec<usize>, Vec<usize>)> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut found = vec![0];
+        let mut seen = HashSet::from([0]);
+        let mut next = 0;
+        while next < found.len() {
+            for m in g3_turns() {
+                let moved = corner_table()[found[next]][m];
+                if seen.insert(moved) {
+                    found.push(moved);
+                }
+            }
+            next += 1;
+        }
+        let mut ranks = vec![usize::MAX; CORNER_PERMUTATIONS];
+        for (rank, &index) in found.iter().enumerate() {
+            ranks[index] = rank;
+        }
+        (found, ranks)
+    })
+}
+
+/// The rank of each set of four U and D layer positions, one bit for each position
+fn m_ranks() -> &'static [usize] {
+    static TABLE: OnceLock<Vec<usize>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let layers = 0b1111_0000_1111;
+        let mut ranks = vec![usize::MAX; 1 << 12];
+        for (rank, mask) in (0..1 << 12)
+            .filter(|&mask: &usize| mask.count_ones() == 4 && mask & !layers == 0)
+            .enumerate()
+        {
+            ranks[mask] = rank;
+        }
+        ranks
+    })
+}
+
+fn slice_mask(positions: [usize; 4]) -> usize {
+    positions
+        .iter()
+        .fold(0, |mask, &position| mask | 1 << position)
+}
+
+/// The ordering of the corners, and where the M slice edges are, which is solved when the cube
+/// is in G3, given that it is in G2
+fn g2_index(corners: usize, m_mask: usize) -> usize {
+    corners * M_PLACEMENTS + m_ranks()[m_mask]
+}
+
+/// Which placement of the M slice edges each placement leads to, for each of [`FACE_TURNS`]
+fn m_table() -> &'static [[usize; 18]] {
+    static TABLE: OnceLock<Vec<[usize; 18]>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        m_masks()
+            .iter()
+            .map(|&mask| {
+                let mut table = [0; 18];
+                for m in g2_turns() {
+                    let moved = (0..12)
+                        .filter(|position| mask >> position & 1 == 1)
+                        .fold(0, |moved, position| {
+                            moved | 1 << edge_moves()[m][position].0
+                        });
+                    table[m] = m_ranks()[moved];
+                }
+                table
+            })
+            .collect()
+    })
+}
+
+fn turn_g2(index: usize, turn: usize) -> usize {
+    let (corners, m_rank) = (index / M_PLACEMENTS, index % M_PLACEMENTS);
+    corner_table()[corners][turn] * M_PLACEMENTS + m_table()[m_rank][turn]
+}
+
+/// Every set of four U and D layer positions, in the order of [`m_ranks`]
+fn m_masks() -> &'static [usize] {
+    static TABLE: OnceLock<Vec<usize>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut masks = vec![0; M_PLACEMENTS];
+        for (mask, &rank) in m_ranks().iter().enumerate() {
+            if rank != usize::MAX {
+                masks[rank] = mask;
+            }
+        }
+        masks
+    })
+}
+
+/// The number of moves needed to reach G3 from each state of G2
+fn g2_distances() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let (corners, _) = half_turn_corners();
+        let m_mask = slice_mask(SLICES[0]);
+        let solved: Vec<usize> = corners
+            .iter()
+            .map(|&corners| g2_index(corners, m_mask))
+            .collect();
+        distances(
+            CORNER_PERMUTATIONS * M_PLACEMENTS,
+            &solved,
+            &g2_turns(),
+            turn_g2,
+        )
+    })
+}
+
+/// How the edges in each slice are ordered, which is only meaningful when every edge is in its
+/// slice
+fn slice_orderings(edges: [usize; 12]) -> usize {
+    SLICES.iter().fold(0, |index, slice| {
+        let ordering = slice.map(|position| {
+            slice
+                .iter()
+                .position(|&home| home == edges[position])
+                .unwrap_or_default()
+        });
+        index * 24 + permutation_index(&ordering)
+    })
+}
+
+fn edges_from_orderings(index: usize) -> [usize; 12] {
+    let mut edges = [0; 12];
+    let mut rest = index;
+    for slice in SLICES.iter().rev() {
+        let ordering: [usize; 4] = permutation_from_index(rest % 24);
+        rest /= 24;
+        for (position, home) in slice.iter().zip(ordering) {
+            edges[*position] = slice[home];
+        }
+    }
+    edges
+}
+
+/// Which ordering of the edges each ordering leads to, for each of [`FACE_TURNS`] that keeps
+/// the edges in their slices
+fn edge_table() -> &'static [[usize; 18]] {
+    static TABLE: OnceLock<Vec<[usize; 18]>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        (0..SLICE_ORDERINGS)
+            .map(|index| {
+                let edges = edges_from_orderings(index);
+                let mut table = [0; 18];
+                for m in g3_turns() {
+                    let mut turned = [0; 12];
+                    for (position, &edge) in edges.iter().enumerate() {
+                        turned[edge_moves()[m][position].0] = edge;
+                    }
+                    table[m] = slice_orderings(turned);
+                }
+                table
+            })
+            .collect()
+    })
+}
+
+fn turn_g3(index: usize, turn: usize) -> usize {
+    let (corners, edges) = half_turn_corners();
+    let (corner_rank, ordering) = (index / SLICE_ORDERINGS, index % SLICE_ORDERINGS);
+    let moved = corner_table()[corners[corner_rank]][turn];
+    edges[moved] * SLICE_ORDERINGS + edge_table()[ordering][turn]
+}
+
+/// The number of moves needed to solve each state of G3
+fn g3_distances() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        distances(
+            HALF_TURN_CORNERS * SLICE_ORDERINGS,
+            &[0],
+            &g3_turns(),
+            turn_g3,
+        )
+    })
+}
+
+fn corner_index(cube: &Cube) -> usize {
+    permutation_index(&cube.corner_pieces().map(|(home, _)| home))
+}
+
+fn edges(cube: &Cube) -> [usize; 12] {
+    cube.edge_pieces().map(|(home, _)| home)
+}
+
+fn first(step: &Step, index: usize) -> Algorithm {
+    step.solutions(index, 1)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+        .into()
+}
+
+/// Finds the smallest of Thistlethwaite's groups that the cube is in, from 0 for any state to 4
+/// for the solved cube
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::thistlethwaite};
+///
+/// let cube = Cube::new().apply(Algorithm::from("U R2 F2").unwrap());
+///
+/// assert_eq!(thistlethwaite::group(&cube), 2);
+/// ```
+#[must_use]
+pub fn group(cube: &Cube) -> usize {
+    if !dr::is_eo(cube, Axis::FB) {
+        return 0;
+    }
+    if !dr::is_dr(cube, Axis::UD) {
+        return 1;
+    }
+    let m_mask = (0..12)
+        .filter(|&position| SLICES[0].contains(&edges(cube)[position]))
+        .fold(0, |mask, position| mask | 1 << position);
+    if g2_distances()[g2_index(corner_index(cube), m_mask)] != 0 {
+        return 2;
+    }
+    let (_, ranks) = half_turn_corners();
+    let index = ranks[corner_index(cube)] * SLICE_ORDERINGS + slice_orderings(edges(cube));
+    if index == 0 {
+        4
+    } else {
+        3
+    }
+}
+
+/// Solves the cube one phase at a time, returning the moves of each phase
+///
+/// The moves at index `i` take the cube from G`i` into G`i + 1`, and are empty when it is
+/// already there
+#[must_use]
+pub fn solve(cube: &Cube) -> [Algorithm; 4] {
+    let g1 = dr::eo(cube, Axis::FB)
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+    let cube = cube.apply(g1.clone());
+
+    let g2 = dr::dr_keeping(&cube, Axis::UD, Axis::FB, 1)
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+    let cube = cube.apply(g2.clone());
+
+    let m_mask = (0..12)
+        .filter(|&position| SLICES[0].contains(&edges(&cube)[position]))
+        .fold(0, |mask, position| mask | 1 << position);
+    let g3 = first(
+        &Step {
+            distances: g2_distances(),
+            turns: g2_turns(),
+            turn: turn_g2,
+        },
+        g2_index(corner_index(&cube), m_mask),
+    );
+    let cube = cube.apply(g3.clone());
+
+    let (_, ranks) = half_turn_corners();
+    let solved = first(
+        &Step {
+            distances: g3_distances(),
+            turns: g3_turns(),
+            turn: turn_g3,
+        },
+        ranks[corner_index(&cube)] * SLICE_ORDERINGS + slice_orderings(edges(&cube)),
+    );
+    [g1, g2, g3, solved]
+}
+
+#[cfg(test)]
+mod thistlethwaite_tests {
+    use super::*;
+    use crate::moves::Move;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn half_turns_solve_96_corner_orderings() {
+        let (corners, _) = half_turn_corners();
+
+        assert_eq!(corners.len(), HALF_TURN_CORNERS);
+    }
+
+    #[test]
+    fn each_phase_reaches_the_next_group() {
+        let scramble = Algorithm::from(
+            "R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F",
+        )
+        .unwrap();
+        let mut cube = Cube::new().apply(scramble.clone());
+        let phases = solve(&cube);
+
+        assert_eq!(group(&cube), 0);
+        for (i, phase) in phases.iter().enumerate() {
+            cube = cube.apply(phase.clone());
+            assert_eq!(group(&cube), i + 1, "after {phase}");
+        }
+        let solution = phases
+            .iter()
+            .fold(Algorithm::new(), |all, phase| all + phase);
+        assert!(solution.solves(&scramble));
+    }
+
+    #[test]
+    fn phases_only_use_the_moves_of_their_group() {
+        let scramble = Algorithm::from("D2 F' D2 U2 F' L2 D R2 D B2 F L2 R' F' D U'").unwrap();
+        let [_, g2, g3, solved] = solve(&Cube::new().apply(scramble));
+
+        assert!(g2
+            .moves
+            .iter()
+            .all(|m| !matches!(m, Move::FaceTurn(FaceTurn::F(1 | 3) | FaceTurn::B(1 | 3)))));
+        assert!(g3.moves.iter().all(|m| match m {
+            Move::FaceTurn(turn) =>
+                g2_turns().contains(&FACE_TURNS.iter().position(|t| t == turn).unwrap()),
+            _ => false,
+        }));
+        assert!(solved
+            .moves
+            .iter()
+            .all(|m| matches!(m, Move::FaceTurn(turn) if turn.face().1 == 2)));
+    }
+
+    #[test]
+    fn groups_are_recognised() {
+        let group_after = |moves| group(&Cube::new().apply(Algorithm::from(moves).unwrap()));
+
+        assert_eq!(group_after(""), 4);
+        assert_eq!(group_after("R2 U2 F2"), 3);
+        assert_eq!(group_after("U R2 F2"), 2);
+        assert_eq!(group_after("U R"), 1);
+        assert_eq!(group_after("F"), 0);
+    }
+}
@@ -0,0 +1,304 @@
+//! Finds the first step of the ZZ method, and checks the blocks built after it
+//!
+//! ZZ starts with the EO line, which orients every edge relative to F and B and solves DF and DB.
+//! After that the first two layers can be built with only `<R, U, L>`, since those turns keep
+//! the edges oriented and never move the line. The EO cross solves all four D edges instead
+//!
+//! # Example
+//!
+//! ```
+//! use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::zz};
+//!
+//! let scramble = Algorithm::from("R' U' F D2 L2 F R2 U2 R2 B D2 L B2 D' B2 L' R' B D2 B U2 L U2 R' U' F").unwrap();
+//! let cube = Cube::new().apply(scramble);
+//! let eoline = zz::eoline(&cube);
+//!
+//! assert!(zz::is_eoline_solved(&cube.apply(eoline)));
+//! ```
+use std::sync::OnceLock;
+
+use crate::{
+    cube::{Cube, Slot},
+    moves::{Algorithm, FaceTurn, Move},
+};
+
+use super::{cross, distances, dr, edge_moves, Deepening, Step, FACE_TURNS};
+
+/// The edges of the line, DF and DB, then the other two D edges, DL and DR
+const EDGES: [[usize; 2]; 2] = [[9, 11], [10, 8]];
+
+/// The number of ways to flip the edges and place two of them
+const LINE_SIZE: usize = (1 << 12) * 144;
+
+/// Which edges are flipped, then where the two `edges` are
+fn line_index(cube: &Cube, edges: [usize; 2]) -> usize {
+    let pieces = cube.edge_pieces();
+    let position = |edge| {
+        pieces
+            .iter()
+            .position(|&(home, _)| home == edge)
+            .expect("every edge is somewhere")
+    };
+    dr::eo_index(cube) * 144 + position(edges[0]) * 12 + position(edges[1])
+}
+
+/// For each set of flipped edges, the set each of [`FACE_TURNS`] leads to
+fn eo_moves() -> &'static [[usize; 18]] {
+    static TABLE: OnceLock<Vec<[usize; 18]>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        (0..1 << 12)
+            .map(|index| std::array::from_fn(|turn| dr::turn_eo(index, turn)))
+            .collect()
+    })
+}
+
+fn turn_line(index: usize, turn: usize) -> usize {
+    let moves = &edge_moves()[turn];
+    eo_moves()[index / 144][turn] * 144 + moves[index / 12 % 12].0 * 12 + moves[index % 12].0
+}
+
+/// The number of moves needed to orient the edges and solve each pair of [`EDGES`]
+fn line_distances() -> &'static [Vec<u8>; 2] {
+    static TABLE: OnceLock<[Vec<u8>; 2]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let turns: Vec<usize> = (0..FACE_TURNS.len()).collect();
+        EDGES.map(|[a, b]| distances(LINE_SIZE, &[a * 12 + b], &turns, turn_line))
+    })
+}
+
+/// Determines if every edge is oriented relative to F and B, and DF and DB are solved
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::zz};
+///
+/// assert!(zz::is_eoline_solved(&Cube::new().apply(Algorithm::from("R U L'").unwrap())));
+/// assert!(!zz::is_eoline_solved(&Cube::new().apply(Algorithm::from("R U F2").unwrap())));
+/// ```
+#[must_use]
+pub fn is_eoline_solved(cube: &Cube) -> bool {
+    line_index(cube, EDGES[0]) == 9 * 12 + 11
+}
+
+/// Determines if every edge is oriented relative to F and B, and all four D edges are solved
+#[must_use]
+pub fn is_eocross_solved(cube: &Cube) -> bool {
+    is_eoline_solved(cube) && line_index(cube, EDGES[1]) == 10 * 12 + 8
+}
+
+/// Determines if the EO line is solved along with the DL edge and the FL and BL pairs
+#[must_use]
+pub fn is_left_block_solved(cube: &Cube) -> bool {
+    is_eoline_solved(cube)
+        && cube.edge_pieces()[10] == (10, false)
+        && cube.is_pair_solved(Slot::FL)
+        && cube.is_pair_solved(Slot::BL)
+}
+
+/// Determines if the EO line is solved along with the DR edge and the FR and BR pairs
+#[must_use]
+pub fn is_right_block_solved(cube: &Cube) -> bool {
+    is_eoline_solved(cube)
+        && cube.edge_pieces()[8] == (8, false)
+        && cube.is_pair_solved(Slot::FR)
+        && cube.is_pair_solved(Slot::BR)
+}
+
+/// Determines if the EO line and both blocks are solved, leaving only the last layer
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::zz};
+///
+/// let cube = Cube::new().apply(Algorithm::from("R U R' U R U2 R'").unwrap());
+///
+/// assert!(zz::is_f2l_solved(&cube));
+/// assert!(!zz::is_f2l_solved(&cube.apply(Algorithm::from("L").unwrap())));
+/// ```
+#[must_use]
+pub fn is_f2l_solved(cube: &Cube) -> bool {
+    is_left_block_solved(cube) && is_right_block_solved(cube)
+}
+
+/// Determines if `moves` only turns R, U and L, which is all that F2L needs after EO line
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{moves::Algorithm, solvers::zz};
+///
+/// assert!(zz::is_rul(&Algorithm::from("R U2 L' U R'").unwrap()));
+/// assert!(!zz::is_rul(&Algorithm::from("R U F").unwrap()));
+/// ```
+#[must_use]
+pub fn is_rul(moves: &Algorithm) -> bool {
+    moves.moves.iter().all(|m| {
+        matches!(
+            m,
+            Move::FaceTurn(FaceTurn::R(_) | FaceTurn::U(_) | FaceTurn::L(_))
+        )
+    })
+}
+
+/// Finds the shortest sequence of face turns that solves the EO line
+///
+/// The moves are empty when the EO line is already solved
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::{Algorithm, Metric},
+///     solvers::zz,
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("D' F R2").unwrap());
+/// let moves = zz::eoline(&cube);
+///
+/// assert_eq!(moves.length(Metric::Htm), 3);
+/// assert!(zz::is_eoline_solved(&cube.apply(moves)));
+/// ```
+#[must_use]
+pub fn eoline(cube: &Cube) -> Algorithm {
+    let step = Step {
+        distances: &line_distances()[0],
+        turns: (0..FACE_TURNS.len()).collect(),
+        turn: turn_line,
+    };
+    step.solutions(line_index(cube, EDGES[0]), 1)
+        .swap_remove(0)
+        .into()
+}
+
+/// Where the search for the EO cross is, as an index for each pair of [`EDGES`] and the cross
+#[derive(Clone, Copy)]
+struct EoCross {
+    lines: [usize; 2],
+    cross: cross::CrossState,
+}
+
+impl EoCross {
+    fn distance(&self) -> u8 {
+        let tables = line_distances();
+        tables[0][self.lines[0]]
+            .max(tables[1][self.lines[1]])
+            .max(cross::distance(self.cross))
+    }
+
+    fn turn(&self, turn: usize) -> Self {
+        Self {
+            lines: self.lines.map(|index| turn_line(index, turn)),
+            cross: cross::turn(self.cross, turn),
+        }
+    }
+}
+
+/// Finds the shortest sequence of face turns that solves the EO cross
+///
+/// The moves are empty when the EO cross is already solved
+///
+/// # Panics
+///
+/// This never panics, as every EO cross can be solved
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, solvers::zz};
+///
+/// let cube = Cube::new().apply(Algorithm::from("F L' D2 R").unwrap());
+/// let moves = zz::eocross(&cube);
+///
+/// assert!(zz::is_eocross_solved(&cube.apply(moves)));
+/// ```
+#[must_use]
+pub fn eocross(cube: &Cube) -> Algorithm {
+    let start = EoCross {
+        lines: EDGES.map(|edges| line_index(cube, edges)),
+        cross: cross::cross_state(cube),
+    };
+    let deepening = Deepening {
+        turns: &(0..FACE_TURNS.len()).collect::<Vec<_>>(),
+        turn: &EoCross::turn,
+        heuristic: &EoCross::distance,
+    };
+    deepening
+        .shortest(&[start])
+        .expect("every EO cross can be solved")
+        .into_iter()
+        .map(|m| FACE_TURNS[m])
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[cfg(test)]
+mod zz_tests {
+    use super::*;
+    use crate::solvers::solvers_tests;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn cube(moves: &str) -> Cube {
+        Cube::new().apply(Algorithm::from(moves).unwrap())
+    }
+
+    fn random_scrambles(count: usize) -> Vec<Cube> {
+        solvers_tests::random_scrambles(50, count)
+            .into_iter()
+            .map(|scramble| Cube::new().apply(scramble))
+            .collect()
+    }
+
+    #[test]
+    fn eoline_is_solved_optimally() {
+        for cube in random_scrambles(20) {
+            let moves = eoline(&cube);
+
+            assert!(is_eoline_solved(&cube.apply(moves.clone())), "{moves}");
+            assert_eq!(
+                moves.moves.len(),
+                usize::from(line_distances()[0][line_index(&cube, EDGES[0])])
+            );
+        }
+    }
+
+    #[test]
+    fn eocross_takes_at_least_as_long_as_eoline() {
+        for cube in random_scrambles(3) {
+            let moves = eocross(&cube);
+
+            assert!(is_eocross_solved(&cube.apply(moves.clone())), "{moves}");
+            assert!(moves.moves.len() >= eoline(&cube).moves.len());
+        }
+    }
+
+    #[test]
+    fn solved_steps_need_no_moves() {
+        assert_eq!(eoline(&cube("R U L'")), Algorithm::new());
+        assert_eq!(eocross(&cube("R U R' U2")), Algorithm::new());
+        assert!(!is_eocross_solved(&cube("D")));
+        assert!(!is_eoline_solved(&cube("D2")));
+    }
+
+    #[test]
+    fn blocks_are_checked_on_each_side() {
+        let cube = cube("R U R'");
+
+        assert!(is_eoline_solved(&cube));
+        assert!(is_left_block_solved(&cube));
+        assert!(!is_right_block_solved(&cube));
+        assert!(!is_left_block_solved(&self::cube("L' U L")));
+        assert!(is_right_block_solved(&self::cube("L' U L")));
+        assert!(!is_left_block_solved(&self::cube("F2")));
+    }
+
+    #[test]
+    fn f2l_moves_are_limited_to_r_u_and_l() {
+        assert!(is_rul(&Algorithm::new()));
+        assert!(!is_rul(&Algorithm::from("R D").unwrap()));
+        assert!(!is_rul(&Algorithm::from("r U").unwrap()));
+    }
+}
@@ -0,0 +1,216 @@
+//! Looks at cubes one sticker at a time, for anything that draws them
+use crate::{
+    cube::{Cube, Face, Side, CORNER_FACES, EDGE_FACES},
+    cube2::Cube2,
+};
+
+/// Where a single sticker is
+///
+/// Rows and columns are counted from 0, as they are laid out in the printed net: the first row of
+/// U is at the back, the first row of D is at the front, and the first row of every other face is
+/// at the top. Columns go left to right, as the face is seen in the net
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Sticker {
+    pub face: Face,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A cube whose stickers can be looked at one by one
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::{Cube, Face, Side},
+///     moves::Algorithm,
+///     stickers::{Sticker, Stickers},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R").unwrap());
+/// let sticker = Sticker { face: Face::U, row: 0, col: 2 };
+///
+/// assert_eq!(cube.color(sticker), Side::Red);
+/// ```
+pub trait Stickers {
+    /// The number of layers of the cube
+    fn size(&self) -> usize;
+
+    /// The color of one sticker
+    fn color(&self, sticker: Sticker) -> Side;
+
+    /// Every sticker of the cube, face by face, then row by row
+    fn stickers(&self) -> Vec<Sticker> {
+        let size = self.size();
+        Face::ALL
+            .into_iter()
+            .flat_map(|face| {
+                (0..size).flat_map(move |row| (0..size).map(move |col| Sticker { face, row, col }))
+            })
+            .collect()
+    }
+}
+
+fn coordinate(value: usize) -> i32 {
+    i32::try_from(value).expect("cubes are not that big")
+}
+
+impl Sticker {
+    /// Where the sticker is on a cube with `size` layers, measured from the center of the cube in
+    /// half-stickers, with x going to R, y going to U, and z going to F
+    pub(crate) fn location(self, size: usize) -> [i32; 3] {
+        let max = coordinate(size - 1);
+        let row = 2 * coordinate(self.row) - max;
+        let col = 2 * coordinate(self.col) - max;
+        match self.face {
+            Face::U => [col, max, row],
+            Face::D => [col, -max, -row],
+            Face::F => [col, -row, max],
+            Face::B => [-col, -row, -max],
+            Face::L => [-max, -row, col],
+            Face::R => [max, -row, -col],
+        }
+    }
+
+    /// The sticker on `face` at `location`, the inverse of [`Sticker::location`]
+    pub(crate) fn at(face: Face, location: [i32; 3], size: usize) -> Self {
+        let max = coordinate(size - 1);
+        let [x, y, z] = location;
+        let (row, col) = match face {
+            Face::U => (z, x),
+            Face::D => (-z, x),
+            Face::F => (-y, x),
+            Face::B => (-y, -x),
+            Face::L => (-y, z),
+            Face::R => (-y, -z),
+        };
+        let index = |v: i32| usize::try_from(v + max).expect("stickers are on the cube") / 2;
+        Self {
+            face,
+            row: index(row),
+            col: index(col),
+        }
+    }
+
+    /// Every face that the piece holding this sticker is on
+    pub(crate) fn piece_faces(self, size: usize) -> Vec<Face> {
+        let location = self.location(size);
+        let max = coordinate(size - 1);
+        Face::ALL
+            .into_iter()
+            .filter(|face| {
+                let normal = face.normal();
+                (0..3).map(|i| normal[i] * location[i]).sum::<i32>() == max
+            })
+            .collect()
+    }
+}
+
+/// Which part of a 3x3 or 2x2 a sticker is on
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Place {
+    /// The position of the corner, and which of its colors the sticker is
+    Corner(usize, usize),
+    /// The position of the edge, and which of its colors the sticker is
+    Edge(usize, usize),
+    Center(Face),
+}
+
+impl Sticker {
+    /// Which piece of a cube with `size` layers this sticker is on, for cubes with at most 3
+    pub(crate) fn place(self, size: usize) -> Place {
+        let faces = self.piece_faces(size);
+        let holds = |piece: &[Face]| faces.iter().all(|face| piece.contains(face));
+        let index = |piece: &[Face]| {
+            piece
+                .iter()
+                .position(|&face| face == self.face)
+                .expect("the sticker is on the piece")
+        };
+        match faces.len() {
+            3 => {
+                let position = CORNER_FACES
+                    .iter()
+                    .position(|piece| holds(piece))
+                    .expect("every corner has a position");
+                Place::Corner(position, index(&CORNER_FACES[position]))
+            }
+            2 => {
+                let position = EDGE_FACES
+                    .iter()
+                    .position(|piece| holds(piece))
+                    .expect("every edge has a position");
+                Place::Edge(position, index(&EDGE_FACES[position]))
+            }
+            _ => Place::Center(self.face),
+        }
+    }
+}
+
+impl Stickers for Cube {
+    fn size(&self) -> usize {
+        3
+    }
+
+    fn color(&self, sticker: Sticker) -> Side {
+        match sticker.place(3) {
+            Place::Corner(position, index) => self.corners[position].colors[index],
+            Place::Edge(position, index) => self.edges[position].colors[index],
+            Place::Center(face) => self.center(face),
+        }
+    }
+}
+
+impl Stickers for Cube2 {
+    fn size(&self) -> usize {
+        2
+    }
+
+    fn color(&self, sticker: Sticker) -> Side {
+        match sticker.place(2) {
+            Place::Corner(position, index) => self.corners[position].colors[index],
+            Place::Edge(..) | Place::Center(_) => unreachable!("a 2x2 only has corners"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod stickers_tests {
+    use super::*;
+    use crate::{moves::Algorithm, nxn::NxNCube};
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    fn colors(cube: &impl Stickers) -> Vec<Side> {
+        cube.stickers().into_iter().map(|s| cube.color(s)).collect()
+    }
+
+    #[test]
+    fn cube_matches_nxn() {
+        let moves = Algorithm::from("R U2 F' x 2R Dw' L 3B2 z' Bw y2 2U").unwrap();
+        let actual = colors(&Cube::new().apply(moves.clone()));
+        let expected = colors(&NxNCube::new(3).apply(moves));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn cube2_matches_nxn() {
+        let moves = Algorithm::from("R U2 F' x D L B2 z' y2").unwrap();
+        let actual = colors(&Cube2::new().apply(moves.clone()));
+        let expected = colors(&NxNCube::new(2).apply(moves));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn locations_round_trip() {
+        for size in 1..=5 {
+            for sticker in NxNCube::new(size).stickers() {
+                let actual = Sticker::at(sticker.face, sticker.location(size), size);
+
+                assert_eq!(actual, sticker);
+            }
+        }
+    }
+}
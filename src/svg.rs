@@ -0,0 +1,389 @@
+//! Draws cubes as SVG images, for alg sheets and web pages
+//!
+//! Every renderer takes anything that implements [`Stickers`], so the same drawings work for
+//! [`Cube`](crate::cube::Cube), [`Cube2`](crate::cube2::Cube2) and
+//! [`NxNCube`](crate::nxn::NxNCube)
+use std::fmt::Write;
+
+use crate::{
+    cube::{Face, Side},
+    mask::Mask,
+    stickers::{Sticker, Stickers},
+};
+
+/// The fill used for each color, and for the outlines of the stickers
+///
+/// Colors can be anything SVG understands, like `"#ffd500"` or `"gold"`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Palette {
+    pub yellow: String,
+    pub white: String,
+    pub red: String,
+    pub orange: String,
+    pub blue: String,
+    pub green: String,
+    /// The fill of stickers that are masked out
+    pub masked: String,
+    pub border: String,
+}
+
+impl Palette {
+    /// The fill for a color
+    #[must_use]
+    pub fn color(&self, side: Side) -> &str {
+        match side {
+            Side::Yellow => &self.yellow,
+            Side::White => &self.white,
+            Side::Red => &self.red,
+            Side::Orange => &self.orange,
+            Side::Blue => &self.blue,
+            Side::Green => &self.green,
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            yellow: "#ffd500".to_string(),
+            white: "#ffffff".to_string(),
+            red: "#b71234".to_string(),
+            orange: "#ff5800".to_string(),
+            blue: "#0046ad".to_string(),
+            green: "#009b48".to_string(),
+            masked: "#808080".to_string(),
+            border: "#000000".to_string(),
+        }
+    }
+}
+
+/// How a cube is drawn
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     mask::{Mask, Stage},
+///     svg::{self, SvgOptions},
+/// };
+///
+/// let mut options = SvgOptions::default();
+/// options.palette.masked = "#333333".to_string();
+/// options.mask = Mask::stage(Stage::OLL, 3);
+///
+/// let image = svg::net(&Cube::new(), &options);
+///
+/// assert!(image.contains("#333333"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SvgOptions {
+    pub palette: Palette,
+    /// Hides stickers by drawing them with [`Palette::masked`] instead of their color
+    pub mask: Mask,
+    /// The width of one sticker, in pixels
+    pub sticker_size: u32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            mask: Mask::default(),
+            sticker_size: 30,
+        }
+    }
+}
+
+impl SvgOptions {
+    fn fill(&self, cube: &impl Stickers, sticker: Sticker) -> &str {
+        if self.mask.hides(sticker) {
+            &self.palette.masked
+        } else {
+            self.palette.color(cube.color(sticker))
+        }
+    }
+}
+
+fn image(width: f64, height: f64, shapes: &str) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">{shapes}</svg>"#
+    )
+}
+
+/// Escapes a palette color so it can't end the attribute it is written into
+fn attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+fn rect(out: &mut String, [x, y, width, height]: [f64; 4], fill: &str, border: &str) {
+    let (fill, border) = (attribute(fill), attribute(border));
+    write!(
+        out,
+        r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}" stroke="{border}"/>"#
+    )
+    .expect("writing to a string can't fail");
+}
+
+fn polygon(out: &mut String, points: &[(f64, f64)], fill: &str, border: &str) {
+    let points = points
+        .iter()
+        .map(|(x, y)| format!("{x:.2},{y:.2}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (fill, border) = (attribute(fill), attribute(border));
+    write!(
+        out,
+        r#"<polygon points="{points}" fill="{fill}" stroke="{border}"/>"#
+    )
+    .expect("writing to a string can't fail");
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn float(value: usize) -> f64 {
+    value as f64
+}
+
+/// Draws every face unfolded, laid out the same way as the printed net
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, svg::{self, SvgOptions}};
+///
+/// let image = svg::net(&Cube::new(), &SvgOptions::default());
+///
+/// assert!(image.starts_with("<svg"));
+/// assert_eq!(image.matches("<rect").count(), 54);
+/// ```
+#[must_use]
+pub fn net(cube: &impl Stickers, options: &SvgOptions) -> String {
+    let size = cube.size();
+    let step = f64::from(options.sticker_size);
+    let mut shapes = String::new();
+    for sticker in cube.stickers() {
+        let (x, y) = match sticker.face {
+            Face::U => (size, 0),
+            Face::L => (0, size),
+            Face::F => (size, size),
+            Face::R => (2 * size, size),
+            Face::B => (3 * size, size),
+            Face::D => (size, 2 * size),
+        };
+        let x = float(x + sticker.col) * step;
+        let y = float(y + sticker.row) * step;
+        rect(
+            &mut shapes,
+            [x, y, step, step],
+            options.fill(cube, sticker),
+            &options.palette.border,
+        );
+    }
+    image(float(4 * size) * step, float(3 * size) * step, &shapes)
+}
+
+/// Draws the U face from above, with the top row of each side face folded out around it
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, moves::Algorithm, svg::{self, SvgOptions}};
+///
+/// let t_perm = Algorithm::from("R U R' U' R' F R2 U' R' U' R U R' F'").unwrap();
+/// let image = svg::last_layer(&Cube::new().apply(t_perm), &SvgOptions::default());
+///
+/// assert_eq!(image.matches("<rect").count(), 21);
+/// ```
+#[must_use]
+pub fn last_layer(cube: &impl Stickers, options: &SvgOptions) -> String {
+    let size = cube.size();
+    let step = f64::from(options.sticker_size);
+    let strip = step / 3.0;
+    let far = float(size).mul_add(step, strip);
+    let mut shapes = String::new();
+    let mut draw = |sticker, bounds| {
+        rect(
+            &mut shapes,
+            bounds,
+            options.fill(cube, sticker),
+            &options.palette.border,
+        );
+    };
+    for row in 0..size {
+        for col in 0..size {
+            let x = float(col).mul_add(step, strip);
+            let y = float(row).mul_add(step, strip);
+            draw(
+                Sticker {
+                    face: Face::U,
+                    row,
+                    col,
+                },
+                [x, y, step, step],
+            );
+        }
+    }
+    for col in 0..size {
+        let top = |face| Sticker { face, row: 0, col };
+        let near = float(col).mul_add(step, strip);
+        let reversed = float(size - 1 - col).mul_add(step, strip);
+        draw(top(Face::B), [reversed, 0.0, step, strip]);
+        draw(top(Face::L), [0.0, near, strip, step]);
+        draw(top(Face::R), [far, reversed, strip, step]);
+        draw(top(Face::F), [near, far, step, strip]);
+    }
+    image(far + strip, far + strip, &shapes)
+}
+
+/// Draws the cube in 3D, looking down at the U, F and R faces
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{cube::Cube, svg::{self, SvgOptions}};
+///
+/// let image = svg::isometric(&Cube::new(), &SvgOptions::default());
+///
+/// assert_eq!(image.matches("<polygon").count(), 27);
+/// ```
+#[must_use]
+pub fn isometric(cube: &impl Stickers, options: &SvgOptions) -> String {
+    let size = float(cube.size());
+    let step = f64::from(options.sticker_size);
+    let across = 3f64.sqrt() / 2.0;
+    let project = |[x, y, z]: [f64; 3]| {
+        (
+            (x - z + size) * across * step,
+            (f64::midpoint(x, z) - y + size) * step,
+        )
+    };
+    let mut shapes = String::new();
+    for sticker in cube.stickers() {
+        let row = float(sticker.row);
+        let col = float(sticker.col);
+        let corners = match sticker.face {
+            Face::U => [
+                [col, size, row],
+                [col + 1.0, size, row],
+                [col + 1.0, size, row + 1.0],
+                [col, size, row + 1.0],
+            ],
+            Face::F => [
+                [col, size - row, size],
+                [col + 1.0, size - row, size],
+                [col + 1.0, size - row - 1.0, size],
+                [col, size - row - 1.0, size],
+            ],
+            Face::R => [
+                [size, size - row, size - col],
+                [size, size - row, size - col - 1.0],
+                [size, size - row - 1.0, size - col - 1.0],
+                [size, size - row - 1.0, size - col],
+            ],
+            Face::D | Face::B | Face::L => continue,
+        };
+        polygon(
+            &mut shapes,
+            &corners.map(project),
+            options.fill(cube, sticker),
+            &options.palette.border,
+        );
+    }
+    image(2.0 * size * across * step, 2.0 * size * step, &shapes)
+}
+
+#[cfg(test)]
+mod svg_tests {
+    use super::*;
+    use crate::{cube::Cube, cube2::Cube2, moves::Algorithm, nxn::NxNCube};
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn net_has_every_color() {
+        let options = SvgOptions::default();
+        let image = net(&Cube::new(), &options);
+
+        for side in [
+            Side::Yellow,
+            Side::White,
+            Side::Red,
+            Side::Orange,
+            Side::Blue,
+            Side::Green,
+        ] {
+            let fill = format!(r#"fill="{}""#, options.palette.color(side));
+            assert_eq!(image.matches(&fill).count(), 9);
+        }
+    }
+
+    #[test]
+    fn palette_colors_are_escaped() {
+        let options = SvgOptions {
+            palette: Palette {
+                red: r##"url("#a&b")"##.to_string(),
+                border: "<none>".to_string(),
+                ..Palette::default()
+            },
+            ..SvgOptions::default()
+        };
+        let image = net(&Cube::new(), &options);
+
+        assert_eq!(
+            image.matches(r#"fill="url(&quot;#a&amp;b&quot;)""#).count(),
+            9
+        );
+        assert_eq!(image.matches(r#"stroke="&lt;none>""#).count(), 54);
+        assert!(!image.contains("<none>"));
+    }
+
+    #[test]
+    fn masked_stickers_are_grey() {
+        let options = SvgOptions {
+            mask: Mask::hiding((0..3).map(|col| Sticker {
+                face: Face::F,
+                row: 0,
+                col,
+            })),
+            ..SvgOptions::default()
+        };
+        let image = net(&Cube::new(), &options);
+
+        assert_eq!(image.matches(r##"fill="#808080""##).count(), 3);
+        assert_eq!(image.matches(r##"fill="#b71234""##).count(), 6);
+    }
+
+    #[test]
+    fn last_layer_shows_side_stickers() {
+        let options = SvgOptions::default();
+        let image = last_layer(&Cube::new().apply(Algorithm::from("U").unwrap()), &options);
+
+        assert_eq!(image.matches(r##"fill="#ffd500""##).count(), 9);
+        assert_eq!(image.matches(r##"fill="#009b48""##).count(), 3);
+        assert_eq!(image.matches(r##"fill="#b71234""##).count(), 3);
+    }
+
+    #[test]
+    fn last_layer_places_stickers_around_the_top() {
+        let options = SvgOptions {
+            sticker_size: 30,
+            ..SvgOptions::default()
+        };
+        let image = last_layer(&Cube2::new(), &options);
+
+        assert!(image.contains(r#"<rect x="10" y="0" width="30" height="10""#));
+        assert!(image.contains(r#"<rect x="70" y="40" width="10" height="30""#));
+        assert!(image.contains(r#"width="80" height="80""#));
+    }
+
+    #[test]
+    fn isometric_draws_three_faces() {
+        let image = isometric(&NxNCube::new(4), &SvgOptions::default());
+
+        assert_eq!(image.matches("<polygon").count(), 48);
+        assert_eq!(image.matches(r##"fill="#ffffff""##).count(), 0);
+    }
+}
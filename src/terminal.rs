@@ -0,0 +1,172 @@
+//! Draws cubes as text, for terminals and logs
+//!
+//! The emoji net is what `{}` prints for every cube, and the ANSI net is what `{:#}` prints
+use crate::{
+    cube::{Face, Side},
+    mask::Mask,
+    stickers::{Sticker, Stickers},
+};
+
+/// How each sticker is drawn
+///
+/// Stickers hidden by a [`Mask`] are drawn as `🔲`, grey, or `-`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Style {
+    /// A colored square emoji for each sticker, with black squares around the net
+    #[default]
+    Emoji,
+    /// Two spaces with a truecolor ANSI background for each sticker, which lines up in any
+    /// terminal that supports 24-bit color
+    Ansi,
+    /// The first letter of each color, `Y W R O B G`, for logs that only take plain text
+    Letters,
+}
+
+/// Draws every face unfolded, with U above and D below L, F, R and B
+///
+/// # Example
+///
+/// ```
+/// use rubiks_moves::{
+///     cube::Cube,
+///     moves::Algorithm,
+///     terminal::{self, Style},
+/// };
+///
+/// let cube = Cube::new().apply(Algorithm::from("R").unwrap());
+///
+/// assert_eq!(
+///     terminal::net(&cube, Style::Letters),
+///     "   YYR
+///    YYR
+///    YYR
+/// BBBRRWGGGYOO
+/// BBBRRWGGGYOO
+/// BBBRRWGGGYOO
+///    WWO
+///    WWO
+///    WWO"
+/// );
+/// ```
+#[must_use]
+pub fn net(cube: &impl Stickers, style: Style) -> String {
+    masked_net(cube, style, &Mask::default())
+}
+
+/// Draws every face unfolded like [`net`], with the stickers that `mask` hides greyed out
+#[must_use]
+pub fn masked_net(cube: &impl Stickers, style: Style, mask: &Mask) -> String {
+    let size = cube.size();
+    let row = |faces: &[Face], row: usize| -> Vec<Cell> {
+        faces
+            .iter()
+            .flat_map(|&face| (0..size).map(move |col| Sticker { face, row, col }))
+            .map(|sticker| {
+                if mask.hides(sticker) {
+                    Cell::Hidden
+                } else {
+                    Cell::Color(cube.color(sticker))
+                }
+            })
+            .collect()
+    };
+    let padded = |face, r| {
+        let blank = vec![Cell::Blank; size];
+        [blank.clone(), row(&[face], r), blank.clone(), blank].concat()
+    };
+    let lines: Vec<String> = (0..size)
+        .map(|r| padded(Face::U, r))
+        .chain((0..size).map(|r| row(&[Face::L, Face::F, Face::R, Face::B], r)))
+        .chain((0..size).map(|r| padded(Face::D, r)))
+        .map(|cells| line(&cells, style))
+        .collect();
+    lines.join("\n")
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Cell {
+    Blank,
+    Hidden,
+    Color(Side),
+}
+
+fn line(cells: &[Cell], style: Style) -> String {
+    let cells = cells.iter().map(|&cell| match (style, cell) {
+        (Style::Emoji, Cell::Color(side)) => side.to_string(),
+        (Style::Emoji, Cell::Hidden) => "🔲".to_string(),
+        (Style::Emoji, Cell::Blank) => "⬛".to_string(),
+        (Style::Ansi, Cell::Color(side)) => background(rgb(side)),
+        (Style::Ansi, Cell::Hidden) => background([128, 128, 128]),
+        (Style::Ansi, Cell::Blank) => "  ".to_string(),
+        (Style::Letters, Cell::Color(side)) => letter(side).to_string(),
+        (Style::Letters, Cell::Hidden) => "-".to_string(),
+        (Style::Letters, Cell::Blank) => " ".to_string(),
+    });
+    let line: String = cells.collect();
+    match style {
+        Style::Emoji => line,
+        Style::Ansi | Style::Letters => line.trim_end().to_string(),
+    }
+}
+
+fn background([r, g, b]: [u8; 3]) -> String {
+    format!("\x1b[48;2;{r};{g};{b}m  \x1b[0m")
+}
+
+const fn rgb(side: Side) -> [u8; 3] {
+    match side {
+        Side::Yellow => [255, 213, 0],
+        Side::White => [255, 255, 255],
+        Side::Red => [183, 18, 52],
+        Side::Orange => [255, 88, 0],
+        Side::Blue => [0, 70, 173],
+        Side::Green => [0, 155, 72],
+    }
+}
+
+pub(crate) const fn letter(side: Side) -> char {
+    match side {
+        Side::Yellow => 'Y',
+        Side::White => 'W',
+        Side::Red => 'R',
+        Side::Orange => 'O',
+        Side::Blue => 'B',
+        Side::Green => 'G',
+    }
+}
+
+#[cfg(test)]
+mod terminal_tests {
+    use super::*;
+    use crate::{cube::Cube, cube2::Cube2, moves::Algorithm, nxn::NxNCube};
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    fn alternate_display_is_ansi() {
+        let cube = Cube2::new().apply(Algorithm::from("R U").unwrap());
+
+        assert_str_eq!(format!("{cube:#}"), net(&cube, Style::Ansi));
+    }
+
+    #[test]
+    fn ansi_cells_are_two_columns() {
+        let actual = net(&NxNCube::new(2), Style::Ansi);
+        let first = actual.lines().next().unwrap();
+
+        assert_str_eq!(
+            first,
+            "    \x1b[48;2;255;213;0m  \x1b[0m\x1b[48;2;255;213;0m  \x1b[0m"
+        );
+        assert_eq!(actual.matches("\x1b[48;2;").count(), 24);
+    }
+
+    #[test]
+    fn letters_are_ascii() {
+        let actual = net(&Cube::new(), Style::Letters);
+
+        assert!(actual.is_ascii());
+        assert_eq!(actual.matches('Y').count(), 9);
+        assert_eq!(actual.lines().count(), 9);
+    }
+}